@@ -0,0 +1,107 @@
+//! Generates one integration test per (day, scenario, part) found in `data/days/*/outputs.json`,
+//! and an ignored one for each implemented day that has no expected outputs
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::{env, fs};
+
+#[path = "src/input/data_root.rs"]
+mod data_root;
+
+use data_root::{DATA_DIR_VAR, data_root};
+
+const PARTS: [(&str, &str); 2] = [("part1", "Part1"), ("part2", "Part2")];
+
+/// Test function name, numbered when scenarios only differ in characters that are not allowed in
+/// a name, like `example-1` and `example_1`
+fn test_name(taken: &mut HashSet<String>, day: u32, scenario: &str, part: &str) -> String {
+    let scenario: String = scenario
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let name = format!("day{day:02}_{scenario}_{part}");
+    let name = (1..)
+        .map(|index| match index {
+            1 => name.clone(),
+            _ => format!("{name}_{index}"),
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .expect("Some numbered name to be free");
+    taken.insert(name.clone());
+
+    name
+}
+
+fn main() {
+    println!("cargo::rerun-if-env-changed={DATA_DIR_VAR}");
+
+    let days_dir = data_root(None).join("days");
+    println!("cargo::rerun-if-changed={}", days_dir.display());
+
+    let solutions_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/day/solutions");
+    println!("cargo::rerun-if-changed={}", solutions_dir.display());
+    let mut generated = String::new();
+    let mut taken = HashSet::new();
+
+    for day in 1..=50u32 {
+        let implemented = solutions_dir.join(format!("day{day}.rs")).exists();
+        let outputs_path = days_dir.join(format!("day{day}")).join("outputs.json");
+        let Ok(contents) = fs::read_to_string(&outputs_path) else {
+            if implemented {
+                println!(
+                    "cargo::warning=No expected outputs for day {day} at {}, so its test is ignored",
+                    outputs_path.display()
+                );
+                writeln!(
+                    generated,
+                    "#[test]\n#[ignore = \"no expected outputs at {}\"]\nfn {}() -> anyhow::Result<()> {{\n    common::cases::run_case({day}, \"real\", aoc2019::day::DayPart::Part1)?;\n    common::cases::run_case({day}, \"real\", aoc2019::day::DayPart::Part2)?;\n    common::cases::run_both_case({day}, \"real\")\n}}\n",
+                    outputs_path.display().to_string().escape_default(),
+                    test_name(&mut taken, day, "missing", "outputs"),
+                )
+                .unwrap();
+            }
+            continue;
+        };
+
+        let outputs: serde_json::Value = serde_json::from_str(&contents)
+            .unwrap_or_else(|e| panic!("Failed to parse {outputs_path:?}: {e}"));
+        let Some(scenarios) = outputs.as_object() else {
+            panic!("Expected an object of scenarios in {outputs_path:?}");
+        };
+
+        for (scenario, expected) in scenarios {
+            let present = PARTS
+                .iter()
+                .filter(|(key, _variant)| !expected.get(key).is_none_or(serde_json::Value::is_null))
+                .collect::<Vec<_>>();
+
+            for (key, variant) in &present {
+                writeln!(
+                    generated,
+                    "#[test]\nfn {}() -> anyhow::Result<()> {{\n    common::cases::run_case({day}, {scenario:?}, aoc2019::day::DayPart::{variant})\n}}\n",
+                    test_name(&mut taken, day, scenario, key),
+                )
+                .unwrap();
+            }
+
+            if present.len() == PARTS.len() {
+                writeln!(
+                    generated,
+                    "#[test]\nfn {}() -> anyhow::Result<()> {{\n    common::cases::run_both_case({day}, {scenario:?})\n}}\n",
+                    test_name(&mut taken, day, scenario, "both"),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solution_tests.rs"), generated).unwrap();
+}
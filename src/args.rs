@@ -1,10 +1,74 @@
 use crate::day::DayPart;
-use clap::Parser;
+use crate::input::InputSource;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum PartSelection {
+    Part1,
+    Part2,
+    /// Both parts, from a single parse of the input
+    Both,
+}
+
+impl PartSelection {
+    pub fn single(&self) -> Option<DayPart> {
+        match self {
+            Self::Part1 => Some(DayPart::Part1),
+            Self::Part2 => Some(DayPart::Part2),
+            Self::Both => None,
+        }
+    }
+}
 
 #[derive(Debug, Parser)]
 pub struct Args {
     pub day: u32,
-    pub day_part: DayPart,
-    #[arg(short, long)]
-    pub example: bool,
+    pub day_part: PartSelection,
+    /// Use `example.txt`, or `example{NAME}.txt` when a name is given
+    #[arg(short, long, value_name = "NAME", num_args = 0..=1, default_missing_value = "")]
+    pub example: Option<String>,
+    /// Read the input from this file instead of the data directory
+    #[arg(short, long, conflicts_with_all = ["example", "stdin"])]
+    pub input: Option<PathBuf>,
+    /// Read the input from standard input
+    #[arg(long, conflicts_with = "example")]
+    pub stdin: bool,
+    /// Override a puzzle parameter, e.g. `--param ticks=10`
+    #[arg(short, long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<String>,
+    /// Directory containing `days/`, overriding the `AOC2019_DATA_DIR` environment variable
+    #[arg(long)]
+    pub data_dir: Option<PathBuf>,
+    /// Save a picture of the part's final state; the extension picks PPM, PGM, PNG or SVG
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["visualize", "watch"])]
+    pub export: Option<PathBuf>,
+    /// Pixels per board cell in exported pictures and animations
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub scale: u16,
+    /// Animate the part, saving a `.gif` or asciinema `.cast`, or playing it on the terminal
+    /// when no path is given
+    #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with = "stdin")]
+    pub visualize: Option<Option<PathBuf>>,
+    /// Play the part live on the terminal, in colour and with a status line
+    #[arg(long, conflicts_with = "visualize")]
+    pub watch: bool,
+    /// Frames per second of the animation or live playback
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u16).range(1..))]
+    pub fps: u16,
+    /// Only keep every N-th frame of the animation
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub every: usize,
+}
+
+impl Args {
+    pub fn input_source(&self) -> InputSource {
+        match (&self.input, self.stdin, &self.example) {
+            (Some(path), _, _) => InputSource::Path(path.clone()),
+            (None, true, _) => InputSource::Stdin,
+            (None, false, Some(name)) if name.is_empty() => InputSource::Example(None),
+            (None, false, Some(name)) => InputSource::Example(Some(name.clone())),
+            (None, false, None) => InputSource::Real,
+        }
+    }
 }
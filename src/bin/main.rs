@@ -1,25 +1,77 @@
+use anyhow::Context;
 use aoc2019::args::Args;
-use aoc2019::day::solutions;
-use aoc2019::parsers::parse_file;
+use aoc2019::day::{DayPart, solutions};
+use aoc2019::input::data_root;
+use aoc2019::visualize::{Recorder, sink_for, watch};
 use clap::Parser;
 use itertools::Itertools;
 
 fn main() -> Result<(), anyhow::Error> {
     let args = Args::parse();
     let day_solver = solutions::get_day(args.day)?;
-    let path = solutions::file_path(args.day, args.example);
+    let source = args.input_source();
+    let data_root = data_root(args.data_dir.as_deref());
 
-    let lines = parse_file(&path)?;
-    let input = lines.iter().map(String::as_str).collect_vec();
+    let mut params = if source.is_example() {
+        day_solver.example_parameters()
+    } else {
+        day_solver.parameters()
+    };
+    for assignment in &args.params {
+        params.assign(assignment)?;
+    }
 
-    let solution = day_solver.solve_part(args.day_part, args.example, &input)?;
+    let sink = match (&args.visualize, args.watch) {
+        (Some(output), _) => Some(sink_for(output.as_deref(), args.fps, args.scale.into())?),
+        (None, true) => Some(watch(args.fps)),
+        (None, false) => None,
+    };
 
-    println!(
-        "Day {} part {}:\n{}",
-        args.day,
-        args.day_part,
-        solution.to_string()
-    );
+    let solutions = match (args.day_part.single(), sink) {
+        (Some(part), Some(sink)) => {
+            let contents = source.read(&data_root, args.day)?;
+            let input = contents.lines().collect_vec();
+
+            let mut recorder = Recorder::new(sink, args.every);
+            let solution = day_solver
+                .visualize(part, &params, &input, &mut recorder)?
+                .with_context(|| format!("Day {} part {} cannot be visualized", args.day, part))?;
+            recorder.finish()?;
+
+            vec![(part, solution)]
+        }
+        (None, Some(_)) => anyhow::bail!("Pick a single part to visualize"),
+        (Some(part), None) => match &args.export {
+            Some(path) => {
+                let contents = source.read(&data_root, args.day)?;
+                let input = contents.lines().collect_vec();
+
+                let (solution, picture) = day_solver
+                    .picture(part, &params, &input)?
+                    .with_context(|| format!("Day {} has nothing to picture", args.day))?;
+                picture.scaled(args.scale.into()).save(path)?;
+
+                vec![(part, solution)]
+            }
+            None => {
+                let mut input = source.open(&data_root, args.day)?;
+                vec![(part, day_solver.solve_stream(part, &params, &mut input)?)]
+            }
+        },
+        (None, None) if args.export.is_some() => {
+            anyhow::bail!("Pick a single part to export a picture of")
+        }
+        (None, None) => {
+            let contents = source.read(&data_root, args.day)?;
+            let input = contents.lines().collect_vec();
+            let (part1, part2) = day_solver.solve_both(&params, &input)?;
+            DayPart::values().zip([part1, part2]).collect_vec()
+        }
+    };
+
+    for (part, solution) in solutions {
+        println!("Day {} part {}:\n{}", args.day, part, solution.to_string());
+    }
 
     Ok(())
 }
@@ -1,6 +1,13 @@
+use crate::day::params::Params;
+use crate::export::Picture;
+use crate::parsers::read_all;
+use crate::visualize::Recorder;
 use clap::ValueEnum;
+use itertools::Itertools;
 use std::fmt::Display;
+use std::io::BufRead;
 
+pub mod params;
 pub mod solutions;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
@@ -30,17 +37,81 @@ impl DayPart {
     }
 
     pub fn values() -> impl Iterator<Item = DayPart> {
-        [DayPart::Part1, DayPart::Part2, DayPart::Part1]
-            .iter()
-            .copied()
+        [DayPart::Part1, DayPart::Part2].iter().copied()
     }
 }
 
+/// Solutions to part 1 and part 2, in that order
+pub type BothSolutions = (Box<dyn ToString>, Box<dyn ToString>);
+
+/// Solution to a part, along with a picture of the state it ends in
+pub type PicturedSolution = (Box<dyn ToString>, Picture);
+
 pub trait DaySolver {
+    /// Parameters understood by this day, with their defaults for the real puzzle
+    fn parameters(&self) -> Params {
+        Params::new()
+    }
+
+    /// Defaults for the puzzle's example inputs, for days whose examples run on different
+    /// settings than the real puzzle
+    fn example_parameters(&self) -> Params {
+        self.parameters()
+    }
+
     fn solve_part(
         &self,
         part: DayPart,
-        example: bool,
+        params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error>;
+
+    /// Solve a part straight from a stream, which days with huge inputs can override to avoid
+    /// holding the whole input as lines
+    fn solve_stream(
+        &self,
+        part: DayPart,
+        params: &Params,
+        input: &mut dyn BufRead,
+    ) -> Result<Box<dyn ToString>, anyhow::Error> {
+        let contents = read_all(input)?;
+        let lines = contents.lines().collect_vec();
+
+        self.solve_part(part, params, &lines)
+    }
+
+    /// Solve both parts, which days can override to share parsing and intermediate state
+    fn solve_both(
+        &self,
+        params: &Params,
+        input: &[&str],
+    ) -> Result<BothSolutions, anyhow::Error> {
+        let part1 = self.solve_part(DayPart::Part1, params, input)?;
+        let part2 = self.solve_part(DayPart::Part2, params, input)?;
+
+        Ok((part1, part2))
+    }
+
+    /// Solve a part along with a picture of the state it ends in, for days that draw something
+    /// worth looking at
+    fn picture(
+        &self,
+        _part: DayPart,
+        _params: &Params,
+        _input: &[&str],
+    ) -> Result<Option<PicturedSolution>, anyhow::Error> {
+        Ok(None)
+    }
+
+    /// Solve a part while handing snapshots of its state to the recorder, for days that can be
+    /// animated
+    fn visualize(
+        &self,
+        _part: DayPart,
+        _params: &Params,
+        _input: &[&str],
+        _recorder: &mut Recorder,
+    ) -> Result<Option<Box<dyn ToString>>, anyhow::Error> {
+        Ok(None)
+    }
 }
@@ -0,0 +1,132 @@
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamValue {
+    Int(i64),
+    Bool(bool),
+    Text(String),
+}
+
+impl ParamValue {
+    /// Parse a raw value into the same kind of value as this one
+    fn parse_like(&self, raw: &str) -> Result<Self, anyhow::Error> {
+        let value = match self {
+            Self::Int(_) => Self::Int(raw.parse()?),
+            Self::Bool(_) => Self::Bool(raw.parse()?),
+            Self::Text(_) => Self::Text(raw.to_owned()),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<i64> for ParamValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+/// Named puzzle parameters, declared by each day with typed defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, ParamValue>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, default: impl Into<ParamValue>) -> Self {
+        self.values.insert(name.to_owned(), default.into());
+        self
+    }
+
+    /// Override a declared parameter, parsing the raw value as the type of its default
+    pub fn set(&mut self, name: &str, raw: &str) -> Result<(), anyhow::Error> {
+        let known = self.values.keys().join(", ");
+        let value = self
+            .values
+            .get_mut(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown parameter {name}, expected one of: {known}"))?;
+
+        *value = value
+            .parse_like(raw)
+            .map_err(|e| anyhow::anyhow!("Invalid value {raw:?} for parameter {name}: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Override a parameter from a `name=value` assignment
+    pub fn assign(&mut self, assignment: &str) -> Result<(), anyhow::Error> {
+        let (name, raw) = assignment
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected name=value, found {assignment:?}"))?;
+
+        self.set(name.trim(), raw.trim())
+    }
+
+    fn get(&self, name: &str) -> Result<&ParamValue, anyhow::Error> {
+        self.values
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Undeclared parameter {name}"))
+    }
+
+    pub fn int<T: TryFrom<i64>>(&self, name: &str) -> Result<T, anyhow::Error> {
+        match self.get(name)? {
+            &ParamValue::Int(value) => T::try_from(value)
+                .map_err(|_| anyhow::anyhow!("Parameter {name} out of range: {value}")),
+            other => Err(anyhow::anyhow!("Parameter {name} is not an integer: {other}")),
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Result<bool, anyhow::Error> {
+        match self.get(name)? {
+            &ParamValue::Bool(value) => Ok(value),
+            other => Err(anyhow::anyhow!("Parameter {name} is not a boolean: {other}")),
+        }
+    }
+
+    pub fn text(&self, name: &str) -> Result<&str, anyhow::Error> {
+        match self.get(name)? {
+            ParamValue::Text(value) => Ok(value),
+            other => Err(anyhow::anyhow!("Parameter {name} is not text: {other}")),
+        }
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let assignments = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .join(", ");
+
+        write!(f, "{assignments}")
+    }
+}
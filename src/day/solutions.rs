@@ -10,12 +10,12 @@ use crate::day::solutions::day8::Day8;
 use crate::day::solutions::day9::Day9;
 use crate::day::solutions::day10::Day10;
 use crate::day::solutions::day11::Day11;
-use std::path::{Path, PathBuf};
 use crate::day::solutions::day12::Day12;
 use crate::day::solutions::day13::Day13;
 use crate::day::solutions::day14::Day14;
 use crate::day::solutions::day15::Day15;
 use crate::day::solutions::day16::Day16;
+use crate::day::solutions::day18::Day18;
 
 mod day1;
 mod day10;
@@ -33,12 +33,13 @@ mod day13;
 mod day14;
 mod day15;
 mod day16;
+mod day18;
 
 pub fn get_day(day: u32) -> Result<Box<dyn DaySolver>, anyhow::Error> {
     match day {
         invalid if invalid == 0 || invalid > 50 => anyhow::bail!("Invalid day {invalid}"),
         1 => Ok(Box::new(Day1::new())),
-        2 => Ok(Box::new(Day2::new(19690720))),
+        2 => Ok(Box::new(Day2::new())),
         3 => Ok(Box::new(Day3::new())),
         4 => Ok(Box::new(Day4::new())),
         5 => Ok(Box::new(Day5::new())),
@@ -53,19 +54,7 @@ pub fn get_day(day: u32) -> Result<Box<dyn DaySolver>, anyhow::Error> {
 	    14 => Ok(Box::new(Day14::new())),
 	    15 => Ok(Box::new(Day15::new())),
 	    16 => Ok(Box::new(Day16::new())),
+	    18 => Ok(Box::new(Day18::new())),
         _unimplemented => anyhow::bail!("Unimplemented day: {day}"),
     }
 }
-
-pub fn file_path(day: u32, example: bool) -> Box<Path> {
-    let mut path = PathBuf::from("data/days/");
-    path.push(format!("day{day}/"));
-    path.push("input/");
-    if example {
-        path.push("example.txt")
-    } else {
-        path.push("real.txt");
-    }
-
-    path.into_boxed_path()
-}
@@ -1,4 +1,8 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
+use crate::parsers::{ParseResult, parse_lines, stream_lines, value};
+use itertools::Itertools;
+use std::io::BufRead;
 
 pub struct Day1 {}
 
@@ -7,15 +11,6 @@ impl Day1 {
         Self {}
     }
 
-    fn parse_input(input: &[&str]) -> Result<Vec<u32>, anyhow::Error> {
-        let numbers = input
-            .iter()
-            .map(|line| line.parse())
-            .collect::<Result<Vec<u32>, _>>()?;
-
-        Ok(numbers)
-    }
-
     fn calc_fuel(mass: u32, looping: bool) -> u32 {
         if mass == 0 {
             return 0;
@@ -35,14 +30,48 @@ impl DaySolver for Day1 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        let total_fuel: u32 = Self::parse_input(input)?
+        let total_fuel: u32 = parse_lines::<u32>(input)?
             .into_iter()
             .map(|module_mass| Self::calc_fuel(module_mass, part.is_part2()))
             .sum();
 
         Ok(Box::new(total_fuel))
     }
+
+    fn solve_stream(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &mut dyn BufRead,
+    ) -> Result<Box<dyn ToString>, anyhow::Error> {
+        let total_fuel = stream_lines(input, value::<u32>)
+            .map_ok(|module_mass| Self::calc_fuel(module_mass, part.is_part2()))
+            .sum::<ParseResult<u32>>()?;
+
+        Ok(Box::new(total_fuel))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream() {
+        let masses = ["12", "14", "1969", "100756"];
+        let params = Params::new();
+
+        for (part, expected) in DayPart::values().zip(["34241", "51316"]) {
+            let solved = Day1::new().solve_part(part, &params, &masses).unwrap();
+            assert_eq!(solved.to_string(), expected);
+
+            let streamed = Day1::new()
+                .solve_stream(part, &params, &mut masses.join("\n").as_bytes())
+                .unwrap();
+            assert_eq!(streamed.to_string(), expected);
+        }
+    }
 }
@@ -1,16 +1,19 @@
-use crate::day::{DayPart, DaySolver};
+use crate::day::params::Params;
+use crate::day::{DayPart, DaySolver, PicturedSolution};
+use crate::export::{Picture, Rgb};
 use crate::intcode::IntMachine;
 use crate::parsers::parse_intmachine_input;
 use crate::shared::board::{Board, Grid, HashBoard};
 use crate::shared::coord::{Coord, Direction};
+use crate::shared::ocr::read_letters;
 use crate::types::IntCell;
+use crate::visualize::{Frame, Recorder};
 use std::collections::HashSet;
 
 pub(crate) struct Day11 {}
 
 type Paint = bool;
 const BLACK: Paint = false;
-#[allow(dead_code)]
 const WHITE: Paint = true;
 
 struct Robot {
@@ -80,38 +83,103 @@ impl Robot {
     fn count_painted(&self) -> usize {
         self.painted.len()
     }
+
+    /// Panels are white or black, with black panels that were painted over drawn gray
+    fn color(&self, coord: Coord, paint: Paint) -> Rgb {
+        match (paint, self.painted.contains(&coord)) {
+            (WHITE, _) => Rgb::WHITE,
+            (BLACK, true) => Rgb::GRAY,
+            (BLACK, false) => Rgb::BLACK,
+        }
+    }
+
+    fn frame(&self) -> anyhow::Result<Frame> {
+        let frame = Frame::from_board(&self.board, |coord, &paint| {
+            if coord == self.pos {
+                (self.dir.arrow(), Rgb::RED)
+            } else if paint == WHITE {
+                ('#', self.color(coord, paint))
+            } else {
+                ('.', self.color(coord, paint))
+            }
+        })?;
+
+        Ok(frame
+            .with_status("Painted", self.count_painted())
+            .with_status("Position", self.pos))
+    }
 }
 
 impl Day11 {
     pub(crate) fn new() -> Self {
         Self {}
     }
+
+    fn paint_hull(part: DayPart, input: &[&str]) -> anyhow::Result<Robot> {
+        Self::paint_hull_stepwise(part, input, |_robot| Ok(()))
+    }
+
+    /// Like `paint_hull`, showing the robot to `on_step` before it starts and after every move
+    fn paint_hull_stepwise(
+        part: DayPart,
+        input: &[&str],
+        mut on_step: impl FnMut(&Robot) -> anyhow::Result<()>,
+    ) -> anyhow::Result<Robot> {
+        let memory = parse_intmachine_input(input)?;
+        let machine = IntMachine::new(memory);
+        let mut robot = Robot::new(machine, part.is_part2());
+
+        on_step(&robot)?;
+        while !robot.tick()? {
+            on_step(&robot)?;
+        }
+
+        Ok(robot)
+    }
+
+    fn answer(part: DayPart, robot: &Robot) -> anyhow::Result<Box<dyn ToString>> {
+        match part {
+            DayPart::Part1 => Ok(Box::new(robot.count_painted())),
+            DayPart::Part2 => Ok(Box::new(read_letters(&robot.get_grid())?)),
+        }
+    }
 }
 
 impl DaySolver for Day11 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> anyhow::Result<Box<dyn ToString>> {
-        let memory = parse_intmachine_input(input)?;
-        let machine = IntMachine::new(memory);
-        let mut robot = Robot::new(machine, part.is_part2());
+        let robot = Self::paint_hull(part, input)?;
 
-        match part {
-            DayPart::Part1 => {
-                while !robot.tick()? {}
+        Self::answer(part, &robot)
+    }
 
-                Ok(Box::new(robot.count_painted()))
-            }
-            DayPart::Part2 => {
-                while !robot.tick()? {}
-                let grid = robot.get_grid().map(|paint| if paint { '#' } else { ' ' });
-                let response = grid.to_string();
+    fn picture(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+    ) -> anyhow::Result<Option<PicturedSolution>> {
+        let robot = Self::paint_hull(part, input)?;
+        let picture = Picture::from_board(&robot.board, |coord, &paint| robot.color(coord, paint))?;
 
-                Ok(Box::new(response))
-            }
-        }
+        Ok(Some((Self::answer(part, &robot)?, picture)))
+    }
+
+    fn visualize(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+        recorder: &mut Recorder,
+    ) -> anyhow::Result<Option<Box<dyn ToString>>> {
+        let robot =
+            Self::paint_hull_stepwise(part, input, |robot| recorder.tick(|| robot.frame()))?;
+        recorder.last(|| robot.frame())?;
+
+        Self::answer(part, &robot).map(Some)
     }
 }
@@ -1,139 +1,109 @@
-use crate::day::{DayPart, DaySolver};
+use crate::day::params::Params;
+use crate::day::{BothSolutions, DayPart, DaySolver};
+use crate::export::Rgb;
+use crate::parsers::{ParseResult, capture, lines, record};
+use crate::shared::board::{Board, HashBoard};
+use crate::shared::coord::Coord;
+use crate::shared::nbody::{self, Body, Simulation};
 use crate::shared::vect3::Vect3;
+use crate::visualize::{Frame, Recorder};
+use anyhow::Context;
 use itertools::Itertools;
-use num::Integer;
 use regex::Regex;
-use std::cmp::Ordering;
-use std::fmt::Display;
+use std::fmt::Write;
+use std::fs;
 use std::sync::LazyLock;
 
-#[derive(Clone, Eq, PartialEq)]
-struct Body {
-    pos: Vect3,
-    vel: Vect3,
+/// Text recorded while simulating, for each log asked for by the parameter of the same name
+#[derive(Default)]
+struct Logs {
+    trace: Option<String>,
+    energy_csv: Option<String>,
 }
 
-impl Body {
-    fn new(pos: Vect3) -> Self {
-        Self {
-            pos,
-            vel: Default::default(),
-        }
-    }
-
-    fn move_(&mut self) {
-        self.pos += self.vel;
-    }
-
-    fn accelerate(&mut self, gravity: Vect3) {
-        self.vel += gravity;
-    }
+impl Logs {
+    const NAMES: [&str; 2] = ["trace", "energy_csv"];
 
-    fn accel_towards_body(&self, other: &Self) -> Vect3 {
-        let diff = other.pos - self.pos;
-        Vect3::new(
-            Self::diff_to_accel(diff.x),
-            Self::diff_to_accel(diff.y),
-            Self::diff_to_accel(diff.z),
-        )
-    }
-
-    fn diff_to_accel(diff: i32) -> i32 {
-        match diff.cmp(&0) {
-            Ordering::Less => -1,
-            Ordering::Equal => 0,
-            Ordering::Greater => 1,
-        }
-    }
+    fn requested(params: &Params) -> anyhow::Result<Self> {
+        let requested = |name| {
+            params
+                .text(name)
+                .map(|path| (!path.is_empty()).then(String::new))
+        };
 
-    fn vect3_value(vect3: &Vect3) -> u32 {
-        vect3.x.unsigned_abs() + vect3.y.unsigned_abs() + vect3.z.unsigned_abs()
+        Ok(Self {
+            trace: requested("trace")?,
+            energy_csv: requested("energy_csv")?,
+        })
     }
 
-    fn potential_energy(&self) -> u32 {
-        Self::vect3_value(&self.pos)
+    fn is_empty(&self) -> bool {
+        self.trace.is_none() && self.energy_csv.is_none()
     }
 
-    fn kinetic_energy(&self) -> u32 {
-        Self::vect3_value(&self.vel)
-    }
-
-    fn total_energy(&self) -> u32 {
-        self.potential_energy() * self.kinetic_energy()
-    }
+    /// Every body along with its velocity, in the puzzle's format with columns lined up
+    fn trace_step(trace: &mut String, simulation: &Simulation<3>) -> std::fmt::Result {
+        let rows = simulation
+            .bodies()
+            .iter()
+            .map(|body| {
+                [body.pos, body.vel]
+                    .into_iter()
+                    .flat_map(|vect3| vect3.axes())
+                    .map(|value| value.to_string())
+                    .collect_vec()
+            })
+            .collect_vec();
+        let widths = (0..6)
+            .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0).max(2))
+            .collect_vec();
 
-    fn all_vect3s(&self) -> Vec<Vect3> {
-        vec![self.pos, self.vel]
+        let steps = simulation.ticks();
+        writeln!(trace, "After {steps} step{}:", if steps == 1 { "" } else { "s" })?;
+        for row in rows {
+            let [x, y, z, vx, vy, vz] = std::array::from_fn(|column| {
+                format!("{:>width$}", row[column], width = widths[column])
+            });
+            writeln!(trace, "pos=<x={x}, y={y}, z={z}>, vel=<x={vx}, y={vy}, z={vz}>")?;
+        }
+        writeln!(trace)
     }
-}
 
-impl Display for Body {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "pos={}, vel={}", self.pos, self.vel)
-    }
-}
-
-#[derive(Clone, Eq, PartialEq)]
-struct Simulation {
-    bodies: Vec<Body>,
-}
+    /// Energy of every body and their total, one row per tick
+    fn energy_row(csv: &mut String, simulation: &Simulation<3>) -> std::fmt::Result {
+        if csv.is_empty() {
+            let bodies = (0..simulation.bodies().len()).map(|index| format!("body{index}"));
+            writeln!(csv, "tick,{},total", bodies.format(","))?;
+        }
 
-impl Simulation {
-    fn new(bodies: Vec<Body>) -> Self {
-        Self { bodies }
+        let energies = simulation.bodies().iter().map(Body::total_energy);
+        writeln!(
+            csv,
+            "{},{},{}",
+            simulation.ticks(),
+            energies.format(","),
+            simulation.total_energy()
+        )
     }
 
-    fn tick(&mut self) {
-        for left_idx in 0..self.bodies.len() {
-            for right_idx in 0..self.bodies.len() {
-                if left_idx == right_idx {
-                    continue;
-                }
-
-                let accelerating = self.bodies.get(left_idx).unwrap();
-                let attracting = self.bodies.get(right_idx).unwrap();
-
-                let acceleration = accelerating.accel_towards_body(attracting);
-
-                self.bodies[left_idx].accelerate(acceleration);
-            }
+    fn record(&mut self, simulation: &Simulation<3>) -> std::fmt::Result {
+        if let Some(trace) = &mut self.trace {
+            Self::trace_step(trace, simulation)?;
         }
-
-        for body in &mut self.bodies {
-            body.move_();
+        if let Some(csv) = &mut self.energy_csv {
+            Self::energy_row(csv, simulation)?;
         }
-    }
 
-    fn total_energy(&self) -> u32 {
-        self.bodies.iter().map(Body::total_energy).sum()
-    }
-
-    fn all_vect3s(&self) -> Vec<Vect3> {
-        self.bodies.iter().flat_map(Body::all_vect3s).collect_vec()
-    }
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum DimensionSelector {
-    X,
-    Y,
-    Z,
-}
-
-impl DimensionSelector {
-    fn extract_dimension(&self, vect3: &Vect3) -> i32 {
-        match self {
-            Self::X => vect3.x,
-            Self::Y => vect3.y,
-            Self::Z => vect3.z,
-        }
+        Ok(())
     }
-}
 
-impl Display for Simulation {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for body in &self.bodies {
-            writeln!(f, "{body}")?;
+    /// Writes every recorded log to the file its parameter names
+    fn save(&self, params: &Params) -> anyhow::Result<()> {
+        for (name, log) in Self::NAMES.into_iter().zip([&self.trace, &self.energy_csv]) {
+            if let Some(log) = log {
+                let path = params.text(name)?;
+                fs::write(path, log).with_context(|| format!("Failed to write {path}"))?;
+            }
         }
 
         Ok(())
@@ -141,7 +111,7 @@ impl Display for Simulation {
 }
 
 static VECT3_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^<x=([+-]?\d+), y=([+-]?\d+), z=([+-]?\d+)>$").unwrap());
+    LazyLock::new(|| Regex::new(r"^<x=(?<x>[+-]?\d+), y=(?<y>[+-]?\d+), z=(?<z>[+-]?\d+)>$").unwrap());
 
 pub(crate) struct Day12 {}
 
@@ -150,98 +120,139 @@ impl Day12 {
         Self {}
     }
 
-    fn parse_vect3(vect3: &str) -> anyhow::Result<Vect3> {
-        let captures = VECT3_PATTERN
-            .captures(vect3)
-            .ok_or_else(|| anyhow::anyhow!("Could not match {vect3}"))?;
-
-        let (_full, [x, y, z]) = captures.extract();
-        let x = x.parse::<i32>()?;
-        let y = y.parse::<i32>()?;
-        let z = z.parse::<i32>()?;
+    fn parse_vect3(vect3: &str) -> ParseResult<Vect3> {
+        let captures = record(&VECT3_PATTERN, vect3)?;
 
-        Ok(Vect3::new(x, y, z))
+        Ok(Vect3::new(
+            capture(&captures, "x")?,
+            capture(&captures, "y")?,
+            capture(&captures, "z")?,
+        ))
     }
 
-    fn parse_input(input: &[&str]) -> anyhow::Result<Simulation> {
-        let positions = input
-            .iter()
-            .copied()
-            .map(Self::parse_vect3)
-            .collect::<anyhow::Result<Vec<_>>>()?;
+    fn parse_input(input: &[&str]) -> ParseResult<Simulation<3>> {
+        let positions = lines(input, Self::parse_vect3)?;
 
         let bodies = positions.into_iter().map(Body::new).collect_vec();
 
         Ok(Simulation::new(bodies))
     }
 
-    fn equal_by_dimension(
-        lhs: &Simulation,
-        rhs: &Simulation,
-        dimension_selector: DimensionSelector,
-    ) -> bool {
-        let lefts = lhs
-            .all_vect3s()
-            .into_iter()
-            .map(|vect3| dimension_selector.extract_dimension(&vect3))
-            .collect_vec();
+    /// Simulation of the input's bodies, pulling each other as the `pull` parameter says
+    fn simulation(input: &[&str], params: &Params) -> anyhow::Result<Simulation<3>> {
+        let pull = nbody::pull_named(params.text("pull")?)?;
 
-        let rights = rhs
-            .all_vect3s()
-            .into_iter()
-            .map(|vect3| dimension_selector.extract_dimension(&vect3))
-            .collect_vec();
+        Ok(Self::parse_input(input)?.with_pull(pull))
+    }
 
-        lefts == rights
+    /// Runs the simulation, recording every tick in the logs that were asked for
+    fn simulate(simulation: &mut Simulation<3>, ticks: u64, logs: &mut Logs) -> anyhow::Result<()> {
+        simulation.run_observed(ticks, |simulation| Ok(logs.record(simulation)?))
     }
 
-    fn loop_for_dimension(simulation: &Simulation, dimension_selector: DimensionSelector) -> u64 {
-        let mut current = simulation.clone();
-        current.tick();
-        let mut ticks = 1;
+    /// Energy once the simulation ran for `ticks`, saving the logs named by the parameters
+    fn energy_after(mut simulation: Simulation<3>, params: &Params) -> anyhow::Result<u32> {
+        let ticks = params.int("ticks")?;
+        let mut logs = Logs::requested(params)?;
 
-        while !Self::equal_by_dimension(&current, &simulation, dimension_selector) {
-            current.tick();
-            ticks += 1;
+        if params.bool("by_axis")? {
+            if !logs.is_empty() {
+                anyhow::bail!("Axes simulated on their own cannot be traced tick by tick");
+            }
+
+            simulation.run_by_axis(ticks);
+        } else {
+            Self::simulate(&mut simulation, ticks, &mut logs)?;
+            logs.save(params)?;
         }
 
-        ticks
+        Ok(simulation.total_energy())
+    }
+
+    fn loop_ticks(simulation: &Simulation<3>) -> u64 {
+        let cycle = simulation.cycle();
+        cycle.tail + cycle.period
     }
-}
 
-const SIMULATIONS_TICKS: u32 = 1000;
+    /// Bodies seen from above, projected onto the XY plane, followed by their full state
+    fn frame(simulation: &Simulation<3>) -> anyhow::Result<Frame> {
+        const COLORS: [Rgb; 5] = [Rgb::RED, Rgb::GREEN, Rgb::BLUE, Rgb::YELLOW, Rgb::WHITE];
+
+        let mut board = HashBoard::new(None);
+        for (index, body) in simulation.bodies().iter().enumerate() {
+            board.write(Coord::new(body.pos.x(), body.pos.y()), Some(index))?;
+        }
+
+        let frame = Frame::from_board(&board, |_coord, body| match body {
+            Some(index) => (
+                char::from_digit((index % 36) as u32, 36).unwrap_or('?'),
+                COLORS[index % COLORS.len()],
+            ),
+            None => (' ', Rgb::BLACK),
+        })?;
+
+        Ok(frame
+            .with_note(simulation)
+            .with_status("Energy", simulation.total_energy()))
+    }
+}
 
 impl DaySolver for Day12 {
+    fn parameters(&self) -> Params {
+        Params::new()
+            .with("ticks", 1000)
+            .with("trace", "")
+            .with("energy_csv", "")
+            .with("by_axis", false)
+            .with("pull", "unit")
+    }
+
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        let mut simulation = Self::parse_input(input)?;
+        let simulation = Self::simulation(input, params)?;
 
         match part {
-            DayPart::Part1 => {
-                for _tick in 0..SIMULATIONS_TICKS {
-                    // println!("At tick {tick}:\n{simulation}");
+            DayPart::Part1 => Ok(Box::new(Self::energy_after(simulation, params)?)),
+            DayPart::Part2 => Ok(Box::new(Self::loop_ticks(&simulation))),
+        }
+    }
 
-                    simulation.tick();
-                }
+    fn solve_both(
+        &self,
+        params: &Params,
+        input: &[&str],
+    ) -> Result<BothSolutions, anyhow::Error> {
+        let simulation = Self::simulation(input, params)?;
+        let loop_ticks = Self::loop_ticks(&simulation);
+        let energy = Self::energy_after(simulation, params)?;
 
-                // println!("Final:\n{simulation}");
+        Ok((Box::new(energy), Box::new(loop_ticks)))
+    }
 
-                Ok(Box::new(simulation.total_energy()))
-            }
-            DayPart::Part2 => {
-                let x_loops = Self::loop_for_dimension(&simulation, DimensionSelector::X);
-                let y_loops = Self::loop_for_dimension(&simulation, DimensionSelector::Y);
-                let z_loops = Self::loop_for_dimension(&simulation, DimensionSelector::Z);
+    fn visualize(
+        &self,
+        part: DayPart,
+        params: &Params,
+        input: &[&str],
+        recorder: &mut Recorder,
+    ) -> Result<Option<Box<dyn ToString>>, anyhow::Error> {
+        // Finding the loop takes far too many ticks to be worth watching
+        if part.is_part2() {
+            return Ok(None);
+        }
 
-	            let loop_ticks = x_loops.lcm(&y_loops).lcm(&z_loops);
+        let mut simulation = Self::simulation(input, params)?;
 
-                Ok(Box::new(loop_ticks))
-            }
-        }
+        simulation.run_observed(params.int("ticks")?, |simulation| {
+            recorder.tick(|| Self::frame(simulation))
+        })?;
+        recorder.last(|| Self::frame(&simulation))?;
+
+        Ok(Some(Box::new(simulation.total_energy())))
     }
 }
 
@@ -256,4 +267,121 @@ mod tests {
             Vect3::new(-1, 0, 2)
         );
     }
+
+    const EXAMPLE: [&str; 4] = [
+        "<x=-1, y=0, z=2>",
+        "<x=2, y=-10, z=-7>",
+        "<x=4, y=-8, z=8>",
+        "<x=3, y=5, z=-1>",
+    ];
+
+    const EXAMPLE_TRACE: &str = "\
+After 0 steps:
+pos=<x=-1, y=  0, z= 2>, vel=<x= 0, y= 0, z= 0>
+pos=<x= 2, y=-10, z=-7>, vel=<x= 0, y= 0, z= 0>
+pos=<x= 4, y= -8, z= 8>, vel=<x= 0, y= 0, z= 0>
+pos=<x= 3, y=  5, z=-1>, vel=<x= 0, y= 0, z= 0>
+
+After 1 step:
+pos=<x= 2, y=-1, z= 1>, vel=<x= 3, y=-1, z=-1>
+pos=<x= 3, y=-7, z=-4>, vel=<x= 1, y= 3, z= 3>
+pos=<x= 1, y=-7, z= 5>, vel=<x=-3, y= 1, z=-3>
+pos=<x= 2, y= 2, z= 0>, vel=<x=-1, y=-3, z= 1>
+
+After 2 steps:
+pos=<x= 5, y=-3, z=-1>, vel=<x= 3, y=-2, z=-2>
+pos=<x= 1, y=-2, z= 2>, vel=<x=-2, y= 5, z= 6>
+pos=<x= 1, y=-4, z=-1>, vel=<x= 0, y= 3, z=-6>
+pos=<x= 1, y=-4, z= 2>, vel=<x=-1, y=-6, z= 2>
+
+";
+
+    #[test]
+    fn logs() {
+        let mut simulation = Day12::parse_input(&EXAMPLE).expect("Example to parse");
+        let mut logs = Logs {
+            trace: Some(String::new()),
+            energy_csv: Some(String::new()),
+        };
+
+        Day12::simulate(&mut simulation, 2, &mut logs).expect("Simulation to run");
+        assert_eq!(logs.trace.as_deref(), Some(EXAMPLE_TRACE));
+
+        Day12::simulate(&mut simulation, 8, &mut logs).expect("Simulation to run");
+        let csv = logs.energy_csv.expect("Energy to be logged");
+        let rows = csv.lines().collect_vec();
+        assert_eq!(rows.len(), 13);
+        assert_eq!(rows[0], "tick,body0,body1,body2,body3,total");
+        assert_eq!(rows[1], "0,0,0,0,0,0");
+        assert_eq!(rows[12], "10,36,45,80,18,179");
+    }
+
+    #[test]
+    fn saved_logs() {
+        let dir = std::env::temp_dir().join(format!("aoc2019-day12-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("Temporary directory to be created");
+        let (trace, csv) = (dir.join("trace.txt"), dir.join("energy.csv"));
+
+        let mut params = Day12::new().parameters();
+        for assignment in [
+            "ticks=2".to_owned(),
+            format!("trace={}", trace.display()),
+            format!("energy_csv={}", csv.display()),
+        ] {
+            params.assign(&assignment).expect("Parameter to be set");
+        }
+
+        let energy = Day12::new()
+            .solve_part(DayPart::Part1, &params, &EXAMPLE)
+            .expect("Day 12 to solve");
+        let saved = (fs::read_to_string(&trace), fs::read_to_string(&csv));
+        fs::remove_dir_all(&dir).expect("Temporary directory to be removed");
+
+        assert_eq!(energy.to_string(), "245");
+        assert_eq!(saved.0.expect("Trace to be saved"), EXAMPLE_TRACE);
+        assert_eq!(saved.1.expect("Energy to be saved").lines().count(), 4);
+
+        params.set("by_axis", "true").expect("By axis to be a parameter");
+        assert!(
+            Day12::new()
+                .solve_part(DayPart::Part1, &params, &EXAMPLE)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn examples() {
+        let mut params = Day12::new().parameters();
+        params.set("ticks", "10").expect("Ticks to be a parameter");
+
+        let solved = Day12::new()
+            .solve_both(&params, &EXAMPLE)
+            .expect("Day 12 to solve");
+        assert_eq!(solved.0.to_string(), "179");
+        assert_eq!(solved.1.to_string(), "2772");
+
+        params.set("by_axis", "true").expect("By axis to be a parameter");
+        let energy = Day12::new()
+            .solve_part(DayPart::Part1, &params, &EXAMPLE)
+            .expect("Day 12 to solve by axis");
+        assert_eq!(energy.to_string(), "179");
+
+        let mut repelling = Day12::parse_input(&EXAMPLE)
+            .expect("Example to parse")
+            .with_pull(nbody::repel_pull);
+        repelling.run(10);
+        params.set("pull", "repel").expect("Pull to be a parameter");
+        let energy = Day12::new()
+            .solve_part(DayPart::Part1, &params, &EXAMPLE)
+            .expect("Day 12 to solve with repelling bodies");
+        assert_eq!(energy.to_string(), repelling.total_energy().to_string());
+        assert_ne!(energy.to_string(), "179");
+
+        params.set("pull", "push").expect("Pull to be a parameter");
+        assert!(
+            Day12::new()
+                .solve_part(DayPart::Part1, &params, &EXAMPLE)
+                .is_err()
+        );
+    }
 }
@@ -1,9 +1,12 @@
-use crate::day::{DayPart, DaySolver};
+use crate::day::params::Params;
+use crate::day::{DayPart, DaySolver, PicturedSolution};
+use crate::export::{Picture, Rgb};
 use crate::intcode::IntMachine;
 use crate::parsers::parse_intmachine_input;
-use crate::shared::board::{Board, HashBoard};
+use crate::shared::board::{Board, BoardExt, HashBoard};
 use crate::shared::coord::Coord;
 use crate::types::IntCell;
+use crate::visualize::{Frame, Recorder};
 use anyhow::{Context, Error};
 use itertools::Itertools;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -19,6 +22,18 @@ enum Tile {
     Ball = 4,
 }
 
+impl From<Tile> for Rgb {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Empty => Rgb::BLACK,
+            Tile::Wall => Rgb::GRAY,
+            Tile::Block => Rgb::BLUE,
+            Tile::Paddle => Rgb::WHITE,
+            Tile::Ball => Rgb::RED,
+        }
+    }
+}
+
 impl From<Tile> for char {
     fn from(value: Tile) -> Self {
         match value {
@@ -68,9 +83,12 @@ impl Day13 {
         Self {}
     }
 
+    /// Paints the machine's output on the board, keeping the previous `score` when the output
+    /// has no score update
     fn parse_machine_output(
         board: &mut HashBoard<Tile>,
         machine: &mut IntMachine,
+        score: IntCell,
     ) -> anyhow::Result<ParsedOutput> {
         let outputs: Vec<DisplayOutput> = machine
             .get_output()
@@ -80,7 +98,7 @@ impl Day13 {
             .with_context(|| "Failed to parse paint instructions from output")?;
         machine.clear_output();
 
-        let mut player_score = 0;
+        let mut player_score = score;
 
         for display_output in outputs {
             match display_output {
@@ -94,34 +112,14 @@ impl Day13 {
         }
 
         let ball = board
-            .elements()
-	        .into_iter()
-            .find_map(|(coord, tile)| {
-                if tile == Tile::Ball {
-                    Some(coord)
-                } else {
-                    None
-                }
-            })
+            .find(&Tile::Ball)
             .ok_or(anyhow::anyhow!("Could not find ball"))?;
 
         let paddle = board
-            .elements()
-	        .into_iter()
-            .find_map(|(coord, tile)| {
-                if tile == Tile::Paddle {
-                    Some(coord)
-                } else {
-                    None
-                }
-            })
+            .find(&Tile::Paddle)
             .ok_or(anyhow::anyhow!("Could not find paddle"))?;
 
-        let blocks = board
-            .elements()
-	        .into_iter()
-            .filter(|(_coord, tile)| *tile == Tile::Block)
-            .count();
+        let blocks = board.positions(&Tile::Block).count();
 
         Ok(ParsedOutput {
             ball,
@@ -130,71 +128,174 @@ impl Day13 {
             blocks,
         })
     }
+
+    /// Screen drawn by the game before any input
+    fn initial_screen(input: &[&str]) -> anyhow::Result<HashBoard<Tile>> {
+        let memory = parse_intmachine_input(input)?;
+        let mut machine = IntMachine::new(memory);
+        machine.run()?;
+
+        let outputs: Vec<DisplayOutput> = machine
+            .get_output()
+            .chunks(3)
+            .map(DisplayOutput::try_from_slice)
+            .try_collect()
+            .with_context(|| "Failed to parse paint instructions from output")?;
+
+        let mut board = HashBoard::new(Tile::Empty);
+
+        for display_output in outputs {
+            match display_output {
+                DisplayOutput::PaintTile { tile, coord } => {
+                    board.write(coord, tile)?;
+                }
+                DisplayOutput::Score(_) => {}
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Plays until every block is broken, returning the final score and screen, and showing
+    /// every screen along with the score to `on_tick`
+    fn play(
+        input: &[&str],
+        mut on_tick: impl FnMut(&HashBoard<Tile>, IntCell) -> anyhow::Result<()>,
+    ) -> anyhow::Result<(IntCell, HashBoard<Tile>)> {
+        let memory = parse_intmachine_input(input)?;
+        let mut machine = IntMachine::new(memory);
+        machine.write(0, 2)?; // Play for free!
+        let mut board = HashBoard::new(Tile::Empty);
+        let mut score = 0;
+
+        loop {
+            machine.run_until_input()?;
+
+            let parsed_output = Self::parse_machine_output(&mut board, &mut machine, score)?;
+            score = parsed_output.score;
+            on_tick(&board, score)?;
+
+            if parsed_output.blocks == 0 {
+                break;
+            }
+
+            let joystick = match parsed_output.paddle.x().cmp(&parsed_output.ball.x()) {
+                Ordering::Equal => 0,
+                Ordering::Less => 1,
+                Ordering::Greater => -1,
+            };
+
+            machine.add_input(joystick);
+        }
+
+        Ok((score, board))
+    }
+
+    fn frame(board: &HashBoard<Tile>, score: IntCell) -> anyhow::Result<Frame> {
+        let frame = Frame::from_board(board, |_coord, &tile| (tile.into(), tile.into()))?
+            .with_status("Score", score);
+
+        match board.find(&Tile::Paddle) {
+            Some(paddle) => Ok(frame.with_status("Paddle", paddle)),
+            None => Ok(frame),
+        }
+    }
 }
 
 impl DaySolver for Day13 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, Error> {
-        let memory = parse_intmachine_input(input)?;
-        let mut machine = IntMachine::new(memory);
-
         match part {
             DayPart::Part1 => {
-                machine.run()?;
-                let output = machine.get_output();
-
-                let outputs: Vec<DisplayOutput> = output
-                    .chunks(3)
-                    .map(DisplayOutput::try_from_slice)
-                    .try_collect()
-                    .with_context(|| "Failed to parse paint instructions from output")?;
-
-                let mut board = HashBoard::new(Tile::Empty);
-
-                for display_output in outputs {
-                    match display_output {
-                        DisplayOutput::PaintTile { tile, coord } => {
-                            board.write(coord, tile)?;
-                        }
-                        DisplayOutput::Score(_) => {}
-                    }
-                }
-                let blocks = board
-                    .coord_mapping
-                    .values()
-                    .filter(|&&tile| tile == Tile::Block)
-                    .count();
+                let board = Self::initial_screen(input)?;
+                let blocks = board.positions(&Tile::Block).count();
 
                 Ok(Box::new(blocks))
             }
             DayPart::Part2 => {
-                machine.write(0, 2)?; // Play for free!
-                let mut board = HashBoard::new(Tile::Empty);
+                let (score, _board) = Self::play(input, |_board, _score| Ok(()))?;
 
-                let score = loop {
-                    machine.run_until_input()?;
+                Ok(Box::new(score))
+            }
+        }
+    }
 
-                    let parsed_output = Self::parse_machine_output(&mut board, &mut machine)?;
+    fn picture(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<Option<PicturedSolution>, Error> {
+        let (solution, board): (Box<dyn ToString>, _) = match part {
+            DayPart::Part1 => {
+                let board = Self::initial_screen(input)?;
+                (Box::new(board.positions(&Tile::Block).count()), board)
+            }
+            DayPart::Part2 => {
+                let (score, board) = Self::play(input, |_board, _score| Ok(()))?;
+                (Box::new(score), board)
+            }
+        };
 
-                    if parsed_output.blocks == 0 {
-                        break parsed_output.score;
-                    }
+        let picture = Picture::from_board(&board, |_coord, &tile| tile.into())?;
+        Ok(Some((solution, picture)))
+    }
 
-                    let joystick = match parsed_output.paddle.x.cmp(&parsed_output.ball.x) {
-                        Ordering::Equal => 0,
-                        Ordering::Less => 1,
-                        Ordering::Greater => -1,
-                    };
+    fn visualize(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+        recorder: &mut Recorder,
+    ) -> Result<Option<Box<dyn ToString>>, Error> {
+        match part {
+            DayPart::Part1 => {
+                let board = Self::initial_screen(input)?;
+                recorder.last(|| Self::frame(&board, 0))?;
 
-                    machine.add_input(joystick);
-                };
+                Ok(Some(Box::new(board.positions(&Tile::Block).count())))
+            }
+            DayPart::Part2 => {
+                let (score, board) = Self::play(input, |board, score| {
+                    recorder.tick(|| Self::frame(board, score))
+                })?;
+                recorder.last(|| Self::frame(&board, score))?;
 
-                Ok(Box::new(score))
+                Ok(Some(Box::new(score)))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_carries_over_frames() -> anyhow::Result<()> {
+        let mut board = HashBoard::new(Tile::Empty);
+
+        // Paints a paddle, a ball and a block, then a new score
+        let mut machine = IntMachine::new(vec![
+            104, 1, 104, 2, 104, 3, 104, 2, 104, 1, 104, 4, 104, 0, 104, 0, 104, 2, 104, -1, 104,
+            0, 104, 12, 99,
+        ]);
+        machine.run()?;
+        let parsed = Day13::parse_machine_output(&mut board, &mut machine, 0)?;
+        assert_eq!(parsed.score, 12);
+        assert_eq!(parsed.blocks, 1);
+
+        // Only moves the ball: the score shown stays the last one
+        let mut machine = IntMachine::new(vec![104, 2, 104, 1, 104, 0, 104, 3, 104, 1, 104, 4, 99]);
+        machine.run()?;
+        let parsed = Day13::parse_machine_output(&mut board, &mut machine, parsed.score)?;
+        assert_eq!(parsed.score, 12);
+        assert_eq!(parsed.ball, Coord::new(3, 1));
+
+        Ok(())
+    }
+}
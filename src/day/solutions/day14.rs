@@ -1,10 +1,11 @@
-use crate::day::{DayPart, DaySolver};
+use crate::day::params::Params;
+use crate::day::{BothSolutions, DayPart, DaySolver};
+use crate::parsers::{ParseError, ParseResult, capture, lines, record, separated};
+use crate::shared::graph::{Graph, NodeId};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::ops::Mul;
-use std::str::FromStr;
 use std::sync::LazyLock;
 
 const FUEL: &str = "FUEL";
@@ -16,45 +17,18 @@ struct Chemical {
     count: u64,
 }
 
-impl Chemical {
-    fn with_count(&self, new_count: u64) -> Self {
-        Self {
-            name: self.name.clone(),
-            count: new_count,
-        }
-    }
-}
-
-impl Mul<u64> for Chemical {
-    type Output = Self;
-
-    fn mul(self, rhs: u64) -> Self::Output {
-        Self {
-            name: self.name,
-            count: self.count * rhs,
-        }
-    }
-}
-
 static CHEMICAL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^(?P<count>\d+) (?P<name>\w+)").expect("Chemical pattern to compile")
+    Regex::new(r"^(?P<count>\d+) (?P<name>\w+)$").expect("Chemical pattern to compile")
 });
 
-impl FromStr for Chemical {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let named_captures = CHEMICAL_PATTERN
-            .captures(s)
-            .ok_or(anyhow::anyhow!("Failed to parse chemical"))?;
-
-        let count = &named_captures["count"];
-        let count = count
-            .parse()
-            .map_err(|_| anyhow::anyhow!("Failed to parse count: {count}"))?;
-        let name = named_captures["name"].to_owned();
+impl Chemical {
+    fn parse(chemical: &str) -> ParseResult<Self> {
+        let captures = record(&CHEMICAL_PATTERN, chemical)?;
 
-        Ok(Self { name, count })
+        Ok(Self {
+            name: captures["name"].to_owned(),
+            count: capture(&captures, "count")?,
+        })
     }
 }
 
@@ -70,27 +44,15 @@ struct Reaction {
     output: Chemical,
 }
 
-static REACTION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^(?P<inputs>\d+ \w+(, \d+ \w+)*) => (?P<output>\d+ \w+)$")
-        .expect("Reaction pattern to compile")
-});
+impl Reaction {
+    fn parse(reaction: &str) -> ParseResult<Self> {
+        let (inputs, output) = reaction.split_once(" => ").ok_or_else(|| {
+            ParseError::at_column(reaction.chars().count() + 1, "expected \" => \"")
+        })?;
 
-impl FromStr for Reaction {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let named_captures = REACTION_PATTERN
-            .captures(s)
-            .ok_or(anyhow::anyhow!("Failed to parse chemical: {s}"))?;
-
-        let inputs = &named_captures["inputs"];
-        let output = &named_captures["output"];
-
-        let inputs = inputs
-            .split(", ")
-            .map(Chemical::from_str)
-            .collect::<Result<Vec<_>, _>>()?;
-        let output = Chemical::from_str(output)?;
+        let output_column = inputs.chars().count() + " => ".len();
+        let inputs = separated(inputs, ", ", Chemical::parse)?;
+        let output = Chemical::parse(output).map_err(|e| e.offset(0, output_column))?;
 
         Ok(Self { inputs, output })
     }
@@ -103,116 +65,107 @@ impl Display for Reaction {
     }
 }
 
-struct MiningOperation {
-    cookbook: HashMap<String, (u64, Vec<Chemical>)>,
-    chemical_count: HashMap<String, i64>, // Negative numbers imply we need that amount of the chemical
+/// Reactions as edges from each chemical to the ingredients it is made from, weighted by the
+/// amount of the ingredient one reaction consumes
+struct Nanofactory {
+    graph: Graph<u64>,
+    /// Amount of each chemical a single reaction produces
+    produced: HashMap<NodeId, u64>,
+    /// Every chemical before all the ingredients it is made from
+    order: Vec<NodeId>,
+    fuel: NodeId,
+    ore: NodeId,
 }
 
-impl MiningOperation {
-    fn new(cookbook: HashMap<String, (u64, Vec<Chemical>)>, fuel_requirement: u64) -> Self {
-        Self {
-            cookbook,
-            chemical_count: HashMap::from([(FUEL.to_owned(), -(fuel_requirement as i64))]), // We need 1 FUEL
-        }
-    }
+impl Nanofactory {
+    fn new(reactions: &[Reaction]) -> anyhow::Result<Self> {
+        let mut graph = Graph::new();
+        let mut produced = HashMap::new();
 
-    fn from_reactions(reactions: Vec<Reaction>, fuel_requirement: u64) -> Self {
-        let cookbook: HashMap<_, _> = reactions
-            .into_iter()
-            .map(|reaction| {
-                (
-                    reaction.output.name,
-                    (reaction.output.count, reaction.inputs),
-                )
-            })
-            .collect();
-
-        Self::new(cookbook, fuel_requirement)
-    }
+        for reaction in reactions {
+            let output = graph.node(&reaction.output.name);
+            if produced.insert(output, reaction.output.count).is_some() {
+                anyhow::bail!("More than one reaction produces {}", reaction.output.name);
+            }
 
-    fn pop_next_required(&mut self) -> Option<Chemical> {
-        let needed_chemical = self
-            .chemical_count
-            .iter()
-            .find(|&(name, &count)| name != ORE && count < 0)
-            .map(|(name, count)| Chemical {
-                name: name.to_owned(),
-                count: count.unsigned_abs(),
-            });
-
-        if let Some(chemical) = &needed_chemical {
-            self.chemical_count.remove(&chemical.name);
-        };
-
-        needed_chemical
-    }
+            for input in &reaction.inputs {
+                graph.add_edge(&reaction.output.name, &input.name, input.count);
+            }
+        }
 
-    fn add_to_required(&mut self, chemical: &Chemical) {
-        let entry = self
-            .chemical_count
-            .entry(chemical.name.clone())
-            .or_insert(0);
-        *entry -= chemical.count as i64;
-    }
+        let fuel = graph.id(FUEL)?;
+        let ore = graph.id(ORE)?;
 
-    fn add_to_obtained(&mut self, chemical: &Chemical) {
-        let entry = self
-            .chemical_count
-            .entry(chemical.name.clone())
-            .or_insert(0);
-        *entry += chemical.count as i64;
-    }
+        if let Some(unknown) = graph
+            .nodes()
+            .find(|&chemical| chemical != ore && !produced.contains_key(&chemical))
+        {
+            anyhow::bail!("No reaction produces {}", graph.name(unknown));
+        }
 
-    fn procure_required(&mut self) -> bool {
-        if let Some(needed_chemical) = self.pop_next_required() {
-            let (output_count, required_chemicals) = self.cookbook[&needed_chemical.name].clone();
-            let multiplier = needed_chemical.count.div_ceil(output_count);
-            let extra_procured = output_count * multiplier - needed_chemical.count;
+        let order = graph.topological_sort()?;
 
-            if extra_procured > 0 {
-                self.add_to_obtained(&needed_chemical.with_count(extra_procured));
-            }
+        Ok(Self {
+            graph,
+            produced,
+            order,
+            fuel,
+            ore,
+        })
+    }
 
-            for required in required_chemicals {
-                self.add_to_required(&(required * multiplier))
+    /// Ore needed for the given amount of fuel. Going through chemicals in order means that
+    /// everything needing one is known before deciding how many reactions make it, so leftovers
+    /// are shared between all of them.
+    fn ore_for(&self, fuel: u64) -> u64 {
+        let mut needed = HashMap::from([(self.fuel, fuel)]);
+
+        for &chemical in &self.order {
+            let (Some(&amount), Some(&produced)) =
+                (needed.get(&chemical), self.produced.get(&chemical))
+            else {
+                continue;
+            };
+            let reactions = amount.div_ceil(produced);
+
+            for &(ingredient, count) in self.graph.edges(chemical) {
+                *needed.entry(ingredient).or_default() += reactions * count;
             }
-
-            false
-        } else {
-            true
         }
-    }
 
-    fn ore_required(&self) -> anyhow::Result<u64> {
-        let ore_count = self
-            .chemical_count
-            .get(ORE)
-            .copied()
-            .ok_or(anyhow::anyhow!("ORE requirements not found"))?;
-        if ore_count <= 0 {
-            Ok(ore_count.unsigned_abs())
-        } else {
-            Err(anyhow::anyhow!("Ore requirements not negative"))
-        }
+        needed.get(&self.ore).copied().unwrap_or_default()
     }
-
-	fn solve_for(reactions: Vec<Reaction>, fuel_requirement: u64) -> anyhow::Result<u64> {
-		let mut mining_operation = Self::from_reactions(reactions, fuel_requirement);
-
-		while !mining_operation.procure_required() {}
-        mining_operation.ore_required()
-	}
 }
 
 pub(crate) struct Day14 {}
 
+const ORE_AVAILABLE: u64 = 1_000_000_000_000;
+
 impl Day14 {
     pub(crate) fn new() -> Self {
         Self {}
     }
 
-    fn parse_input(input: &[&str]) -> anyhow::Result<Vec<Reaction>> {
-        input.iter().copied().map(Reaction::from_str).collect()
+    fn parse_input(input: &[&str]) -> ParseResult<Vec<Reaction>> {
+        lines(input, Reaction::parse)
+    }
+
+    fn max_fuel(factory: &Nanofactory, ore_for_1_fuel: u64) -> u64 {
+        let mut min_fuel_target = ORE_AVAILABLE / ore_for_1_fuel;
+        let mut max_fuel_target = min_fuel_target * 2;
+
+        while max_fuel_target - min_fuel_target > 1 {
+            let mid_fuel_target = (min_fuel_target + max_fuel_target) / 2;
+
+            let ore_needed = factory.ore_for(mid_fuel_target);
+            if ore_needed < ORE_AVAILABLE {
+                min_fuel_target = mid_fuel_target;
+            } else {
+                max_fuel_target = mid_fuel_target;
+            }
+        }
+
+        min_fuel_target
     }
 }
 
@@ -220,37 +173,29 @@ impl DaySolver for Day14 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        let reactions = Self::parse_input(input)?;
-	    let ore_for_1_fuel = MiningOperation::solve_for(reactions.clone(), 1)?;
+        let factory = Nanofactory::new(&Self::parse_input(input)?)?;
+        let ore_for_1_fuel = factory.ore_for(1);
 
         match part {
-            DayPart::Part1 => {
-
-                Ok(Box::new(ore_for_1_fuel))
-            }
-            DayPart::Part2 => {
-	            const ORE_AVAILABLE: u64 = 1_000_000_000_000;
-	            let mut min_fuel_target = ORE_AVAILABLE / ore_for_1_fuel;
-	            let mut max_fuel_target = min_fuel_target * 2;
-
-	            while max_fuel_target - min_fuel_target > 1 {
-		            let mid_fuel_target = (min_fuel_target + max_fuel_target) / 2;
-
-		            let ore_needed = MiningOperation::solve_for(reactions.clone(), mid_fuel_target)?;
-		            if ore_needed < ORE_AVAILABLE {
-			            min_fuel_target = mid_fuel_target;
-		            } else {
-			            max_fuel_target = mid_fuel_target;
-		            }
-	            }
-
-	            Ok(Box::new(min_fuel_target))
-            },
+            DayPart::Part1 => Ok(Box::new(ore_for_1_fuel)),
+            DayPart::Part2 => Ok(Box::new(Self::max_fuel(&factory, ore_for_1_fuel))),
         }
     }
+
+    fn solve_both(
+        &self,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<BothSolutions, anyhow::Error> {
+        let factory = Nanofactory::new(&Self::parse_input(input)?)?;
+        let ore_for_1_fuel = factory.ore_for(1);
+        let max_fuel = Self::max_fuel(&factory, ore_for_1_fuel);
+
+        Ok((Box::new(ore_for_1_fuel), Box::new(max_fuel)))
+    }
 }
 
 #[cfg(test)]
@@ -260,7 +205,7 @@ mod test {
     #[test]
     fn parse_reactions() {
         assert_eq!(
-            Reaction::from_str("2 AB, 3 BC, 4 CA => 1 FUEL").unwrap(),
+            Reaction::parse("2 AB, 3 BC, 4 CA => 1 FUEL").unwrap(),
             Reaction {
                 inputs: vec![
                     Chemical {
@@ -284,7 +229,7 @@ mod test {
         );
 
         assert_eq!(
-            Reaction::from_str("9 ORE => 2 A").unwrap(),
+            Reaction::parse("9 ORE => 2 A").unwrap(),
             Reaction {
                 inputs: vec![Chemical {
                     name: "ORE".to_owned(),
@@ -296,5 +241,39 @@ mod test {
                 },
             }
         );
+
+        let error = Day14::parse_input(&["9 ORE => 2 A", "10 ORE, 7x A => 1 B"]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+
+        let error = Reaction::parse("9 ORE => 2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+    }
+
+    #[test]
+    fn ore_for_fuel() {
+        let reactions = [
+            "157 ORE => 5 NZVS",
+            "165 ORE => 6 DCFZ",
+            "44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL",
+            "12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ",
+            "179 ORE => 7 PSHF",
+            "177 ORE => 5 HKGWZ",
+            "7 DCFZ, 7 PSHF => 2 XJWVT",
+            "165 ORE => 2 GPVTF",
+            "3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT",
+        ];
+
+        let solved = Day14::new()
+            .solve_both(&Params::new(), &reactions)
+            .expect("Day 14 to solve");
+        assert_eq!(solved.0.to_string(), "13312");
+        assert_eq!(solved.1.to_string(), "82892753");
+
+        let cyclic = ["1 ORE => 1 A", "1 A, 1 B => 1 FUEL", "1 FUEL => 1 B"];
+        let error = Day14::new()
+            .solve_part(DayPart::Part1, &Params::new(), &cyclic)
+            .err()
+            .expect("Cyclic reactions to fail");
+        assert!(error.to_string().starts_with("cycle through"));
     }
 }
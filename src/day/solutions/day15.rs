@@ -1,16 +1,18 @@
-use crate::day::{DayPart, DaySolver};
+use crate::day::params::Params;
+use crate::day::{BothSolutions, DayPart, DaySolver, PicturedSolution};
+use crate::export::{Picture, Rgb};
 use crate::intcode::IntMachine;
 use crate::parsers::parse_intmachine_input;
-use crate::shared::a_star::maze::{CostCalculator, MazeSolver, MazeState};
-use crate::shared::a_star::{State, a_star};
-use crate::shared::board::{Board, HashBoard};
+use crate::shared::search::maze::{CostCalculator, MazeSolver, MazeState};
+use crate::shared::search::State;
+use crate::shared::board::{Board, BoardExt, HashBoard};
 use crate::shared::coord::{Coord, Direction};
 use crate::types::IntCell;
+use crate::visualize::{Frame, Recorder};
 use anyhow::Error;
 use itertools::Itertools;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
 pub(crate) struct Day15 {}
@@ -19,6 +21,54 @@ impl Day15 {
     pub(crate) fn new() -> Self {
         Self {}
     }
+
+    /// Explore until the oxygen system is found, returning the droid and the system's position
+    fn explore(input: &[&str]) -> anyhow::Result<(Droid, Coord)> {
+        Self::explore_stepwise(input, |_droid| Ok(()))
+    }
+
+    /// Like `explore`, showing the droid to `on_step` before every exploration step
+    fn explore_stepwise(
+        input: &[&str],
+        mut on_step: impl FnMut(&Droid) -> anyhow::Result<()>,
+    ) -> anyhow::Result<(Droid, Coord)> {
+        let memory = parse_intmachine_input(input)?;
+        let mut droid = Droid::new(memory);
+
+        let destination = loop {
+            on_step(&droid)?;
+            if let Some(destination) = droid.explore_closest() {
+                break destination;
+            }
+        };
+
+        Ok((droid, destination))
+    }
+
+    fn path_cells(path: &[Direction]) -> HashSet<Coord> {
+        path.iter()
+            .scan(Coord::default(), |position, &direction| {
+                *position += direction.into();
+                Some(*position)
+            })
+            .collect()
+    }
+}
+
+/// Cells drawn over the explored map, such as a path or the oxygen spread so far
+struct Overlay {
+    cells: HashSet<Coord>,
+    style: (char, Rgb),
+}
+
+impl Overlay {
+    fn none() -> Self {
+        Self::new(HashSet::new(), (' ', Rgb::BLACK))
+    }
+
+    fn new(cells: HashSet<Coord>, style: (char, Rgb)) -> Self {
+        Self { cells, style }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
@@ -37,6 +87,14 @@ impl Tile {
     fn is_unknown(&self) -> bool {
         matches!(self, Self::Unknown)
     }
+
+    fn glyph(&self) -> char {
+        match self {
+            Tile::Unknown => ' ',
+            Tile::Space => '.',
+            Tile::Wall => '#',
+        }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, IntoPrimitive, TryFromPrimitive)]
@@ -123,24 +181,11 @@ impl Droid {
         }
     }
 
-    fn closest_unknown(&self) -> Coord {
-        let mut active = BinaryHeap::from([Reverse((0, self.position))]);
-        let mut seen = HashSet::from([self.position]);
-
-        while let Some(Reverse((cost, coord))) = active.pop() {
-            match self.board.read(coord).expect("Read board") {
-                Tile::Unknown => return coord,
-                Tile::Wall => continue,
-                Tile::Space => {
-                    let candidates = coord.cross().filter(|c| !seen.contains(c)).collect_vec();
-
-                    seen.extend(candidates.clone());
-                    active.extend(candidates.into_iter().map(|c| Reverse((cost + 1, c))));
-                }
-            }
-        }
-
-        panic!("Could not find any unknown tile from {}", self.position);
+    fn closest_unknown(&self) -> Option<Coord> {
+        self.board
+            .bfs(self.position, |_coord, tile| tile.is_space())
+            .map(|(coord, _distance)| coord)
+            .find(|&coord| self.board.read(coord).is_ok_and(Tile::is_unknown))
     }
 
     fn move_(&mut self, direction: Direction) -> MovementResult {
@@ -214,11 +259,9 @@ impl Droid {
 
         let initial_state = MazeState::initial(start, self.board.as_ref(), &maze_solver);
 
-        let final_state =
-            a_star(initial_state).unwrap_or_else(|| panic!("Found no path to {destination}"));
-
-        final_state
+        initial_state
             .path()
+            .unwrap_or_else(|| panic!("Found no path to {destination}"))
             .into_iter()
             .tuple_windows()
             .map(|(from, to)| Direction::try_from(to - from))
@@ -227,7 +270,9 @@ impl Droid {
     }
 
     fn explore_closest(&mut self) -> Option<Coord> {
-        let closest = self.closest_unknown();
+        let closest = self
+            .closest_unknown()
+            .unwrap_or_else(|| panic!("Could not find any unknown tile from {}", self.position));
         let movement_result = self.move_to(closest);
 
         if movement_result.is_destination() {
@@ -276,43 +321,101 @@ impl Droid {
         }
     }
 
-    fn check_if_coord_is_space(&mut self, coord: Coord) -> bool {
-        match self.board.read(coord).expect("Read the board") {
-            Tile::Space => true,
-            Tile::Wall => false,
-            Tile::Unknown => self.move_to(coord).moved_ok(),
+    fn explore_all(&mut self) {
+        self.explore_all_stepwise(|_droid| Ok(()))
+            .expect("Exploring without watching to succeed");
+    }
+
+    /// Like `explore_all`, showing the droid to `on_step` after every move
+    fn explore_all_stepwise(
+        &mut self,
+        mut on_step: impl FnMut(&Self) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        while let Some(unknown) = self.closest_unknown() {
+            self.move_to(unknown);
+            on_step(self)?;
         }
+
+        Ok(())
     }
 
-    fn gas_fill(&mut self) -> u32 {
-        let mut active = vec![self.position];
-        let mut current_distance = 0;
-        let mut seen: HashSet<Coord> = HashSet::new();
+    /// Minutes for the oxygen to fill the whole area from the given source
+    fn gas_fill(&mut self, source: Coord) -> u32 {
+        self.explore_all();
+        self.oxygen_spread(source).0
+    }
 
-        loop {
-            let next_active = active
-                .iter()
-	            .copied()
-                .filter(|coord| !seen.contains(coord))
-                .filter(|coord| self.check_if_coord_is_space(*coord))
-                .flat_map(|coord| coord.cross().collect_vec())
-                .collect_vec();
-
-            seen.extend(active.iter().copied());
-
-            if next_active.is_empty() {
-                return current_distance;
-            }
+    /// Minutes for the oxygen to fill the explored area, and the minute it reaches every cell
+    fn oxygen_spread(&self, source: Coord) -> (u32, HashMap<Coord, u32>) {
+        let distances = self
+            .board
+            .bfs_distances(source, |_coord, tile| tile.is_space());
+        let minutes = distances.values().copied().max().unwrap_or_default();
+
+        (minutes, distances)
+    }
+}
+
+impl Droid {
+    fn style(
+        &self,
+        coord: Coord,
+        tile: &Tile,
+        destination: Option<Coord>,
+        overlay: &Overlay,
+    ) -> (char, Rgb) {
+        if coord == self.position {
+            ('D', Rgb::RED)
+        } else if Some(coord) == destination {
+            ('O', Rgb::BLUE)
+        } else if coord == Coord::default() {
+            ('S', Rgb::GREEN)
+        } else if overlay.cells.contains(&coord) {
+            overlay.style
+        } else {
+            let color = match tile {
+                Tile::Unknown => Rgb::BLACK,
+                Tile::Space => Rgb::WHITE,
+                Tile::Wall => Rgb::GRAY,
+            };
 
-            current_distance += 1;
-            active = next_active;
+            (tile.glyph(), color)
         }
     }
+
+    fn frame(&self, destination: Option<Coord>, overlay: &Overlay) -> anyhow::Result<Frame> {
+        let frame = Frame::from_board(self.board.as_ref(), |coord, tile| {
+            self.style(coord, tile, destination, overlay)
+        })?;
+
+        Ok(frame.with_status("Position", self.position))
+    }
+
+    fn oxygen_frame(
+        &self,
+        source: Coord,
+        distances: &HashMap<Coord, u32>,
+        minute: u32,
+    ) -> anyhow::Result<Frame> {
+        let filled = distances
+            .iter()
+            .filter(|&(_coord, &distance)| distance <= minute)
+            .map(|(&coord, _distance)| coord)
+            .collect();
+        let frame = self.frame(Some(source), &Overlay::new(filled, ('O', Rgb::BLUE)))?;
+
+        Ok(frame.with_status("Minute", minute))
+    }
 }
 
 impl Display for Droid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Droid at {}\n{:?}", self.position, self.board)
+        write!(
+            f,
+            "Droid at {}\n{}",
+            self.position,
+            self.board.render(Tile::glyph, '?')
+        )
     }
 }
 
@@ -320,28 +423,131 @@ impl DaySolver for Day15 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, Error> {
-        let memory = parse_intmachine_input(input)?;
-        let mut droid = Droid::new(memory);
+        let (mut droid, destination) = Self::explore(input)?;
 
-        let destination = loop {
-            if let Some(destination) = droid.explore_closest() {
-                break destination;
+        match part {
+            DayPart::Part1 => {
+                let shortest_path = droid.find_shortest_path(destination);
+
+                Ok(Box::new(shortest_path.len()))
             }
+            DayPart::Part2 => Ok(Box::new(droid.gas_fill(destination))),
+        }
+    }
+
+    fn solve_both(
+        &self,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<BothSolutions, Error> {
+        let (mut droid, destination) = Self::explore(input)?;
+
+        let shortest_path = droid.find_shortest_path(destination);
+        let gas_fill_time = droid.gas_fill(destination);
+
+        Ok((Box::new(shortest_path.len()), Box::new(gas_fill_time)))
+    }
+
+    fn picture(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<Option<PicturedSolution>, Error> {
+        let (mut droid, destination) = Self::explore(input)?;
+
+        let (solution, overlay): (Box<dyn ToString>, _) = match part {
+            DayPart::Part1 => {
+                let path = droid.find_shortest_path(destination);
+                let overlay = Overlay::new(Self::path_cells(&path), ('*', Rgb::YELLOW));
+                (Box::new(path.len()), overlay)
+            }
+            DayPart::Part2 => (Box::new(droid.gas_fill(destination)), Overlay::none()),
         };
 
+        let picture = Picture::from_board(droid.board.as_ref(), |coord, tile| {
+            droid.style(coord, tile, Some(destination), &overlay).1
+        })?;
+
+        Ok(Some((solution, picture)))
+    }
+
+    fn visualize(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+        recorder: &mut Recorder,
+    ) -> Result<Option<Box<dyn ToString>>, Error> {
+        let (mut droid, destination) = Self::explore_stepwise(input, |droid| {
+            recorder.tick(|| droid.frame(None, &Overlay::none()))
+        })?;
+
         match part {
             DayPart::Part1 => {
-                let shortest_path = droid.find_shortest_path(destination);
+                let path = droid.find_shortest_path(destination);
+                let overlay = Overlay::new(Self::path_cells(&path), ('*', Rgb::YELLOW));
+                recorder.tick(|| droid.frame(Some(destination), &overlay))?;
+                recorder.last(|| droid.frame(Some(destination), &overlay))?;
 
-                Ok(Box::new(shortest_path.len()))
+                Ok(Some(Box::new(path.len())))
             }
             DayPart::Part2 => {
-                let gas_fill_time = droid.gas_fill();
-                Ok(Box::new(gas_fill_time - 1))
+                droid.explore_all_stepwise(|droid| {
+                    recorder.tick(|| droid.frame(Some(destination), &Overlay::none()))
+                })?;
+                let (minutes, distances) = droid.oxygen_spread(destination);
+
+                for minute in 0..=minutes {
+                    recorder.tick(|| droid.oxygen_frame(destination, &distances, minute))?;
+                }
+                recorder.last(|| droid.oxygen_frame(destination, &distances, minutes))?;
+
+                Ok(Some(Box::new(minutes)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oxygen_example() {
+        #[rustfmt::skip]
+        let map = [
+            " ##   ",
+            "#..## ",
+            "#.#..#",
+            "#.O.# ",
+            " ###  ",
+        ];
+
+        let mut droid = Droid::new(vec![99]);
+        *droid.board = HashBoard::default();
+        for (y, line) in map.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    '.' | 'O' => Tile::Space,
+                    _ => continue,
+                };
+                droid.board.write(Coord::new(x as i32, y as i32), tile).unwrap();
             }
         }
+        droid.position = Coord::new(1, 1);
+
+        let source = Coord::new(2, 3);
+        assert_eq!(droid.gas_fill(source), 4);
+        assert_eq!(droid.position, Coord::new(1, 1));
+
+        let (minutes, spread) = droid.oxygen_spread(source);
+        assert_eq!((minutes, spread.len()), (4, 8));
+        assert_eq!(spread[&Coord::new(4, 2)], 3);
+        assert_eq!(spread[&Coord::new(2, 1)], 4);
     }
 }
@@ -1,12 +1,14 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
+use crate::parsers::{Digits, ParseResult, digits, single_line};
 use anyhow::Error;
 use itertools::Itertools;
+use std::io::BufRead;
 use std::iter::{repeat_n, successors};
 
 type Int = i32;
 
 const BASE_PATTERN: [Int; 4] = [0, 1, 0, -1];
-const TARGET_ITERATTION: usize = 100;
 const REQUESTED_DIGITS: usize = 8;
 const OFFSET_DIGITS: usize = 7;
 const INPUT_REPEATS: usize = 10_000;
@@ -44,33 +46,17 @@ impl Day16 {
             .collect()
     }
 
-    fn parse_input(input: &[&str]) -> Vec<Int> {
-        match input {
-            &[line] => line
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as Int)
-                .collect(),
-            [] => panic!("No lines!"),
-            lines => panic!("Expected 1 line, founnd {}", lines.len()),
-        }
+    fn parse_input(input: &[&str]) -> ParseResult<Vec<Int>> {
+        let digits = single_line(input, digits)?;
+
+        Ok(digits.into_iter().map(Int::from).collect())
     }
 
     fn from_digits(digits: &[Int]) -> Int {
         digits.iter().fold(0, |acc, &digit| acc * 10 + digit)
     }
-}
-
-impl DaySolver for Day16 {
-    fn solve_part(
-        &self,
-        part: DayPart,
-        example: bool,
-        input: &[&str],
-    ) -> Result<Box<dyn ToString>, Error> {
-        let digits = Self::parse_input(input);
-
-        let iterations = if !example { TARGET_ITERATTION } else { 5 };
 
+    fn solve(part: DayPart, iterations: usize, digits: Vec<Int>) -> String {
         let digits = match part {
             DayPart::Part1 => successors(Some(digits), |digits| Some(Self::apply_fft(digits)))
                 .nth(iterations)
@@ -97,13 +83,45 @@ impl DaySolver for Day16 {
             }
         };
 
-        let digits: String = digits
+        digits
             .into_iter()
             .take(REQUESTED_DIGITS)
             .map(|digit| char::from_digit(digit as u32, 10).unwrap())
-            .collect();
+            .collect()
+    }
+}
+
+impl DaySolver for Day16 {
+    fn parameters(&self) -> Params {
+        Params::new().with("iterations", 100)
+    }
 
-        Ok(Box::new(digits))
+    fn example_parameters(&self) -> Params {
+        self.parameters().with("iterations", 5)
+    }
+
+    fn solve_part(
+        &self,
+        part: DayPart,
+        params: &Params,
+        input: &[&str],
+    ) -> Result<Box<dyn ToString>, Error> {
+        let digits = Self::parse_input(input)?;
+
+        Ok(Box::new(Self::solve(part, params.int("iterations")?, digits)))
+    }
+
+    fn solve_stream(
+        &self,
+        part: DayPart,
+        params: &Params,
+        input: &mut dyn BufRead,
+    ) -> Result<Box<dyn ToString>, Error> {
+        let digits: Vec<Int> = Digits::new(input)
+            .map_ok(Int::from)
+            .collect::<ParseResult<_>>()?;
+
+        Ok(Box::new(Self::solve(part, params.int("iterations")?, digits)))
     }
 }
 
@@ -114,7 +132,7 @@ mod tests {
     #[test]
     fn compute_digit() {
         assert_eq!(
-            Day16::compute_digit(&Day16::parse_input(&["12345678"]), 1),
+            Day16::compute_digit(&Day16::parse_input(&["12345678"]).unwrap(), 1),
             4
         );
     }
@@ -122,8 +140,42 @@ mod tests {
     #[test]
     fn compute_other_digit() {
         assert_eq!(
-            Day16::compute_digit(&Day16::parse_input(&["48226158"]), 2),
+            Day16::compute_digit(&Day16::parse_input(&["48226158"]).unwrap(), 2),
             4
         );
     }
+
+    #[test]
+    fn phases() {
+        let mut params = Day16::new().parameters();
+        params.set("iterations", "4").expect("Iterations to be a parameter");
+
+        let digits = Day16::new()
+            .solve_part(DayPart::Part1, &params, &["12345678"])
+            .expect("Day 16 to solve");
+        assert_eq!(digits.to_string(), "01029498");
+        assert_eq!(
+            Day16::new().example_parameters().int::<usize>("iterations").unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn stream() {
+        let params = Day16::new().parameters();
+        let examples = [
+            (DayPart::Part1, "80871224585914546619083218645595", "24176176"),
+            (DayPart::Part2, "03036732577212944063491565474664", "84462026"),
+        ];
+
+        for (part, input, expected) in examples {
+            let solved = Day16::new().solve_part(part, &params, &[input]).unwrap();
+            assert_eq!(solved.to_string(), expected);
+
+            let streamed = Day16::new()
+                .solve_stream(part, &params, &mut format!("{input}\n").as_bytes())
+                .unwrap();
+            assert_eq!(streamed.to_string(), expected);
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use crate::day::params::Params;
+use crate::day::{DayPart, DaySolver};
+use crate::parsers::{ParseResult, char_map};
+use crate::shared::board::{Board, Grid};
+use crate::shared::coord::Coord;
+use crate::shared::search::compress::PoiGraph;
+use crate::shared::search::keys::{KeyMaze, Poi};
+use crate::shared::search::{State, dijkstra};
+use anyhow::{Context, Error};
+
+const WALL: char = '#';
+const ENTRANCE: char = '@';
+
+pub(crate) struct Day18 {}
+
+impl Day18 {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+
+    fn parse_input(input: &[&str]) -> ParseResult<(Grid<char>, Vec<Coord>)> {
+        let map = char_map(
+            input,
+            |ch| ch == ENTRANCE,
+            |ch| matches!(ch, '#' | '.' | '@' | 'a'..='z' | 'A'..='Z').then_some(ch),
+        )?;
+
+        let entrances = match map.positions(ENTRANCE) {
+            [] | [_] => vec![map.marker(ENTRANCE)?],
+            entrances => entrances.to_vec(),
+        };
+
+        Ok((map.grid, entrances))
+    }
+
+    /// Walls off the entrance and puts a robot in each of the four diagonal cells around it
+    fn split_entrance(grid: &mut Grid<char>, entrance: Coord) -> Result<(), Error> {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let tile = if dx != 0 && dy != 0 { ENTRANCE } else { WALL };
+                grid.write(entrance + Coord::new(dx, dy), tile)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fewest steps for the robots to pick up every key
+    fn collect_keys(grid: &Grid<char>) -> Result<u64, Error> {
+        let graph = PoiGraph::compress(
+            grid,
+            |coord, &ch| Poi::from_char(coord, ch),
+            |&ch| ch != WALL,
+        );
+        let maze = KeyMaze::new(graph);
+
+        let found = dijkstra(maze.initial()).context("Some keys cannot be reached")?;
+
+        Ok(found.state().cost())
+    }
+}
+
+impl DaySolver for Day18 {
+    fn solve_part(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<Box<dyn ToString>, Error> {
+        let (mut grid, entrances) = Self::parse_input(input)?;
+
+        // Maps made for the second part already come with one robot per vault
+        if let (DayPart::Part2, [entrance]) = (part, entrances.as_slice()) {
+            Self::split_entrance(&mut grid, *entrance)?;
+        }
+
+        Ok(Box::new(Self::collect_keys(&grid)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(part: DayPart, input: &[&str]) -> String {
+        Day18::new()
+            .solve_part(part, &Params::new(), input)
+            .expect("Day 18 to solve")
+            .to_string()
+    }
+
+    #[test]
+    fn examples() {
+        #[rustfmt::skip]
+        let shared_route = [
+            "########################",
+            "#...............b.C.D.f#",
+            "#.######################",
+            "#.....@.a.B.c.d.A.e.F.g#",
+            "########################",
+        ];
+        assert_eq!(solve(DayPart::Part1, &shared_route), "132");
+
+        #[rustfmt::skip]
+        let vault = [
+            "#######",
+            "#a.#Cd#",
+            "##...##",
+            "##.@.##",
+            "##...##",
+            "#cB#Ab#",
+            "#######",
+        ];
+        assert_eq!(solve(DayPart::Part2, &vault), "8");
+
+        #[rustfmt::skip]
+        let split = [
+            "#############",
+            "#g#f.D#..h#l#",
+            "#F###e#E###.#",
+            "#dCba@#@BcIJ#",
+            "#############",
+            "#nK.L@#@G...#",
+            "#M###N#H###.#",
+            "#o#m..#i#jk.#",
+            "#############",
+        ];
+        assert_eq!(solve(DayPart::Part2, &split), "72");
+    }
+
+    #[test]
+    fn missing_entrance() {
+        let error = Day18::parse_input(&["#a.b#"]).unwrap_err();
+        assert_eq!(error.message, "marker '@' not found");
+    }
+}
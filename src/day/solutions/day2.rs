@@ -1,31 +1,15 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
 use crate::intcode::IntMachine;
+use crate::parsers::parse_intmachine_input;
 use crate::types::IntCell;
 use anyhow;
 
-pub struct Day2 {
-    target_output: IntCell,
-}
-
-type Memory = Vec<IntCell>;
+pub struct Day2 {}
 
 impl Day2 {
-    pub(crate) fn new(target_output: IntCell) -> Self {
-        Self { target_output }
-    }
-
-    fn parse_line(line: &str) -> Result<Memory, anyhow::Error> {
-        line.split(',')
-            .map(|value| value.parse::<IntCell>().map_err(|e| e.into()))
-            .collect()
-    }
-
-    fn parse_input(lines: &[&str]) -> Result<Memory, anyhow::Error> {
-        match lines {
-            [] => Err(anyhow::anyhow!("No input lines")),
-            [line] => Self::parse_line(line),
-            [..] => Err(anyhow::anyhow!("Too many input lines")),
-        }
+    pub(crate) fn new() -> Self {
+        Self {}
     }
 
     fn solve_for_output(
@@ -53,25 +37,36 @@ impl Day2 {
 }
 
 impl DaySolver for Day2 {
+    fn parameters(&self) -> Params {
+        Params::new()
+            .with("restore_alarm", true)
+            .with("target", 19690720)
+    }
+
+    /// Examples are run as given, without the 1202 program alarm patch
+    fn example_parameters(&self) -> Params {
+        self.parameters().with("restore_alarm", false)
+    }
+
     fn solve_part(
         &self,
         part: DayPart,
-        example: bool,
+        params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        let memory = Day2::parse_input(input)?;
+        let memory = parse_intmachine_input(input)?;
         let mut machine = IntMachine::new(memory);
 
         let result: Box<dyn ToString> = match part {
             DayPart::Part1 => {
-                if !example {
+                if params.bool("restore_alarm")? {
                     machine.write(1, 12).and_then(|()| machine.write(2, 2))?;
                 }
 
                 Box::new(machine.run()?)
             }
             DayPart::Part2 => {
-                let (noun, verb) = Self::solve_for_output(machine, self.target_output)?;
+                let (noun, verb) = Self::solve_for_output(machine, params.int("target")?)?;
                 Box::new(100 * noun + verb)
             }
         };
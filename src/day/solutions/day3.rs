@@ -1,8 +1,10 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
+use crate::parsers::{ParseError, ParseResult, lines, separated, value};
 use crate::shared::coord::{Coord, Direction};
+use crate::shared::segment::Segment;
 use anyhow::Error;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
@@ -51,30 +53,32 @@ impl Display for Wire {
 }
 
 impl Wire {
-    fn iter(&self) -> impl Iterator<Item = Direction> {
-        self.instructions.iter().flat_map(|instruction| {
-            std::iter::repeat_n(instruction.direction, instruction.length as usize)
-        })
-    }
+    /// Segments walked in order, each with the number of steps taken before reaching it
+    fn segments(&self) -> Vec<(u32, Segment)> {
+        let mut steps = 0;
+        let mut position = Coord::default();
 
-    fn positions(&self) -> impl Iterator<Item = Coord> {
-        self.iter().scan(Coord::default(), |acc, direction| {
-            *acc += direction.into();
-            Some(*acc)
-        })
-    }
+        self.instructions
+            .iter()
+            .map(|instruction| {
+                let segment = Segment::walk(position, instruction.direction, instruction.length);
+                let walked = (steps, segment);
 
-    fn earliest_position(&self) -> HashMap<Coord, u32> {
-        self.positions()
-            .enumerate()
-            .map(|(i, position)| (position, i as u32 + 1))
-            .collect_vec()
-            .into_iter()
-            .rev()
+                steps += instruction.length;
+                position = segment.to();
+
+                walked
+            })
             .collect()
     }
 }
 
+/// Place where both wires are, with the steps each wire took to get there
+struct Crossing {
+    coord: Coord,
+    combined_steps: u32,
+}
+
 type Wires = (Wire, Wire);
 
 pub struct Day3 {}
@@ -84,24 +88,56 @@ impl Day3 {
         Self {}
     }
 
-    fn parse_line(line: &str) -> anyhow::Result<Wire> {
-        let instructions = line.split(',').map(Instruction::from_str).try_collect()?;
+    fn parse_line(line: &str) -> ParseResult<Wire> {
+        let instructions = separated(line, ",", value)?;
 
         Ok(Wire { instructions })
     }
 
-    fn parse_input(input: &[&str]) -> anyhow::Result<Wires> {
-        match input {
-            [first, second] => {
-                let first = Self::parse_line(first)?;
-                let second = Self::parse_line(second)?;
-                Ok((first, second))
-            }
-            lines => Err(anyhow::anyhow!(
-                "Expected 2 wires to parse, found {}",
-                lines.len()
-            )),
-        }
+    fn parse_input(input: &[&str]) -> ParseResult<Wires> {
+        let wires = lines(input, Self::parse_line)?;
+
+        wires.into_iter().collect_tuple().ok_or_else(|| {
+            ParseError::new(
+                input.len().max(1),
+                1,
+                format!("expected 2 wires to parse, found {}", input.len()),
+            )
+        })
+    }
+
+    /// Every place the wires cross, apart from the origin they both start from.
+    ///
+    /// Overlapping segments share a whole run of cells, but only a few of them can be the
+    /// closest to the origin or the earliest to reach: the ends of the run, and the cells around
+    /// the one nearest the origin.
+    fn crossings(first: &Wire, second: &Wire) -> Vec<Crossing> {
+        let second_segments = second.segments();
+
+        first
+            .segments()
+            .into_iter()
+            .cartesian_product(&second_segments)
+            .flat_map(|((first_steps, first), &(second_steps, second))| {
+                let shared = first.intersection(&second).into_iter();
+
+                shared.flat_map(move |shared| {
+                    let nearest = shared.closest_to(Coord::default());
+
+                    [shared.from(), shared.to(), nearest]
+                        .into_iter()
+                        .chain(nearest.cross())
+                        .filter(move |&coord| coord != Coord::default() && shared.contains(coord))
+                        .map(move |coord| Crossing {
+                            coord,
+                            combined_steps: first_steps
+                                + first.steps_to(coord).unwrap_or_default()
+                                + second_steps
+                                + second.steps_to(coord).unwrap_or_default(),
+                        })
+                })
+            })
+            .collect()
     }
 }
 
@@ -109,36 +145,27 @@ impl DaySolver for Day3 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, Error> {
         let (first, second) = Self::parse_input(input)?;
+        let crossings = Self::crossings(&first, &second);
 
         match part {
             DayPart::Part1 => {
-                let first_positions: HashSet<_> = first.positions().collect();
-                let second_positions: HashSet<_> = second.positions().collect();
-
-                let closer_crossing = first_positions
-                    .intersection(&second_positions)
-                    .copied()
-                    .min_by_key(Coord::manhattan)
+                let closer_crossing = crossings
+                    .iter()
+                    .map(|crossing| crossing.coord.manhattan())
+                    .min()
                     .ok_or_else(|| anyhow::anyhow!("No crossing found"))?;
 
-                Ok(Box::new(closer_crossing.manhattan()))
+                Ok(Box::new(closer_crossing))
             }
             DayPart::Part2 => {
-                let mapped_first_positions = first.earliest_position();
-                let mapped_second_positions = second.earliest_position();
-
-                let (_earliest_crossing, combined_distance) = mapped_first_positions
+                let combined_distance = crossings
                     .iter()
-                    .flat_map(|(coord, first_distance)| {
-                        mapped_second_positions
-                            .get(coord)
-                            .map(|second_distance| (coord, first_distance + second_distance))
-                    })
-                    .min_by_key(|(_coord, combined_distance)| *combined_distance)
+                    .map(|crossing| crossing.combined_steps)
+                    .min()
                     .ok_or_else(|| anyhow::anyhow!("No crossing found"))?;
 
                 Ok(Box::new(combined_distance.to_string()))
@@ -146,3 +173,42 @@ impl DaySolver for Day3 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(part: DayPart, input: &[&str]) -> String {
+        Day3::new()
+            .solve_part(part, &Params::new(), input)
+            .expect("Day 3 to solve")
+            .to_string()
+    }
+
+    #[test]
+    fn examples() {
+        let input = [
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+            "U62,R66,U55,R34,D71,R55,D58,R83",
+        ];
+        assert_eq!(solve(DayPart::Part1, &input), "159");
+        assert_eq!(solve(DayPart::Part2, &input), "610");
+
+        // Both wires run over the same cells for a while, starting right at the origin
+        let overlapping = ["R8,U5,L5,D3", "R3,U3,R5,U1"];
+        assert_eq!(solve(DayPart::Part1, &overlapping), "1");
+        assert_eq!(solve(DayPart::Part2, &overlapping), "2");
+
+        assert_eq!(Day3::parse_input(&[]).unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn very_long_wires() {
+        let input = [
+            "R100000000,U100000000,L300000000,D400000000",
+            "U50000000,R200000000,D300000000",
+        ];
+        assert_eq!(solve(DayPart::Part1, &input), "150000000");
+        assert_eq!(solve(DayPart::Part2, &input), "300000000");
+    }
+}
@@ -1,7 +1,8 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
+use crate::parsers::{ParseError, ParseResult, separated, single_line, value};
 use anyhow::Error;
 use itertools::Itertools;
-use std::str::FromStr;
 
 pub struct Day4 {}
 impl Day4 {
@@ -9,29 +10,17 @@ impl Day4 {
         Self {}
     }
 
-    fn parse_input(input: &[&str]) -> anyhow::Result<(u32, u32)> {
-        match input {
-            [line] => Self::parse_range(line),
-            lines => Err(anyhow::anyhow!(
-                "Expected 1 line of input, found {}",
-                lines.len()
-            )),
-        }
+    fn parse_input(input: &[&str]) -> ParseResult<(u32, u32)> {
+        single_line(input, Self::parse_range)
     }
 
-    fn parse_range(range: &str) -> anyhow::Result<(u32, u32)> {
-        match range.split('-').collect_vec().as_slice() {
-            &[start, end] => {
-                let range = u32::from_str(start)
-                    .and_then(|start| u32::from_str(end).map(|end| (start, end)))?;
-
-                Ok(range)
-            }
-            _ => Err(anyhow::anyhow!(
-                "Expected a range delimited by '-', found {}",
-                range
-            )),
-        }
+    fn parse_range(range: &str) -> ParseResult<(u32, u32)> {
+        separated(range, "-", value)?
+            .into_iter()
+            .collect_tuple()
+            .ok_or_else(|| {
+                ParseError::at_column(1, format!("expected a range delimited by '-', found {range}"))
+            })
     }
 
     fn number_digits_is_correct(digits: &[u8]) -> bool {
@@ -64,7 +53,7 @@ impl DaySolver for Day4 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, Error> {
         let (min, max) = Self::parse_input(input)?;
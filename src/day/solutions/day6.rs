@@ -1,27 +1,39 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
+use crate::parsers::{ParseError, ParseResult, lines};
+use crate::shared::graph::Graph;
 use anyhow;
-use itertools::Itertools;
-use std::collections::HashMap;
-use std::str::FromStr;
+use anyhow::Context;
+use std::fs;
 
 pub struct Day6 {}
 
+#[derive(Debug)]
 struct Orbit {
     center: String,
     orbiter: String,
 }
 
-impl FromStr for Orbit {
-    type Err = anyhow::Error;
+impl Orbit {
+    fn parse(orbit: &str) -> ParseResult<Self> {
+        let (center, orbiter) = orbit.split_once(')').ok_or_else(|| {
+            ParseError::at_column(orbit.chars().count() + 1, "expected ')' after the center")
+        })?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(')') {
-            Some((center, orbiter)) => Ok(Self {
-                center: center.to_owned(),
-                orbiter: orbiter.to_owned(),
-            }),
-            _ => Err(anyhow::anyhow!("Invalid orbit: {s}")),
+        if center.is_empty() {
+            return Err(ParseError::at_column(1, "missing center"));
         }
+        if orbiter.is_empty() {
+            return Err(ParseError::at_column(
+                center.chars().count() + 2,
+                "missing orbiter",
+            ));
+        }
+
+        Ok(Self {
+            center: center.to_owned(),
+            orbiter: orbiter.to_owned(),
+        })
     }
 }
 
@@ -30,81 +42,104 @@ impl Day6 {
         Self {}
     }
 
-    fn parse_input(input: &[&str]) -> anyhow::Result<Vec<Orbit>> {
-        input.iter().copied().map(Orbit::from_str).collect()
-    }
-
-    fn chain(
-        orbiter: &str,
-        orbiter_mapper: &HashMap<String, String>,
-    ) -> impl Iterator<Item = String> {
-        let mut current_node = Some(orbiter.to_owned());
-
-        std::iter::from_fn(move || {
-            if let Some(node) = &current_node {
-                let emitted_node = node.clone();
-                current_node = orbiter_mapper.get(&emitted_node).cloned();
-                Some(emitted_node)
-            } else {
-                None
-            }
-        })
+    /// Edges from each center to the objects orbiting it
+    fn orbit_graph(orbits: &[Orbit]) -> Graph {
+        Graph::from_edges(
+            orbits
+                .iter()
+                .map(|orbit| (orbit.center.as_str(), orbit.orbiter.as_str(), ())),
+        )
     }
 
-    fn chain_length(orbiter: &str, orbiter_mapper: &HashMap<String, String>) -> u32 {
-        match orbiter_mapper.get(orbiter) {
-            Some(parent) => 1 + Self::chain_length(parent, orbiter_mapper),
-            None => 0,
+    /// Saves the orbits as a Graphviz file when the `dot` parameter names one
+    fn save_dot(graph: &Graph, params: &Params) -> anyhow::Result<()> {
+        let path = params.text("dot")?;
+        if !path.is_empty() {
+            fs::write(path, graph.to_dot(|()| None))
+                .with_context(|| format!("Failed to write {path}"))?;
         }
+
+        Ok(())
     }
 }
 
 impl DaySolver for Day6 {
+    fn parameters(&self) -> Params {
+        Params::new().with("dot", "")
+    }
+
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        let input = Day6::parse_input(input)?;
-        let orbiter_to_center_mapper: HashMap<_, _> = input
-            .into_iter()
-            .map(|Orbit { center, orbiter }| (orbiter, center))
-            .collect();
+        let input = lines(input, Orbit::parse)?;
+        let graph = Self::orbit_graph(&input);
+        Self::save_dot(&graph, params)?;
 
         match part {
             DayPart::Part1 => {
-                let total: u32 = orbiter_to_center_mapper
-                    .keys()
-                    .map(|orbiter| Self::chain_length(orbiter, &orbiter_to_center_mapper))
-                    .sum();
+                // Every object orbits everything between it and the center of mass
+                let total: u32 = graph.depths().into_iter().flatten().sum();
 
                 Ok(Box::new(total))
             }
             DayPart::Part2 => {
-                let you = Self::chain("YOU", &orbiter_to_center_mapper).collect_vec();
-                let santa = Self::chain("SAN", &orbiter_to_center_mapper).collect_vec();
-
-                let you_iter = you.iter().rev();
-                let santa_iter = santa.iter().rev();
-
-                let prefix_size = you_iter
-                    .zip(santa_iter)
-                    .take_while(|(left, right)| left == right)
-                    .count();
-
-                let you_unshared = you.len() - prefix_size;
-                let santa_unshared = santa.len() - prefix_size;
+                let ancestor = graph
+                    .lowest_common_ancestor(graph.id("YOU")?, graph.id("SAN")?)
+                    .ok_or_else(|| anyhow::anyhow!("YOU and SAN orbit unrelated objects"))?;
 
                 /*
                 YOU and SAN aren't actually objects, they're just markers.
                 That means that your real starting point is the center to YOU,
                 and your destination is the center to SAN.
                  */
-                let orbital_transfers = (you_unshared - 2) + (santa_unshared - 2) + 2;
+                let to_center = |distance: u32| {
+                    distance
+                        .checked_sub(1)
+                        .context("YOU and SAN must not orbit one another")
+                };
+                let orbital_transfers =
+                    to_center(ancestor.first_distance)? + to_center(ancestor.second_distance)?;
 
                 Ok(Box::new(orbital_transfers))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(part: DayPart, input: &[&str]) -> String {
+        Day6::new()
+            .solve_part(part, &Day6::new().parameters(), input)
+            .expect("Day 6 to solve")
+            .to_string()
+    }
+
+    #[test]
+    fn examples() {
+        let orbits = [
+            "COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L",
+        ];
+        assert_eq!(solve(DayPart::Part1, &orbits), "42");
+
+        let transfers = [orbits.as_slice(), &["K)YOU", "I)SAN"]].concat();
+        assert_eq!(solve(DayPart::Part2, &transfers), "4");
+
+        let nested = ["COM)B", "B)YOU", "YOU)SAN"];
+        let error = Day6::new()
+            .solve_part(DayPart::Part2, &Day6::new().parameters(), &nested)
+            .err()
+            .expect("YOU to be SAN's center");
+        assert_eq!(error.to_string(), "YOU and SAN must not orbit one another");
+
+        let error = lines(&["COM)B", "BC", "C)"], Orbit::parse).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        let error = Orbit::parse("C)").unwrap_err();
+        assert_eq!(error.column, 3);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
 use crate::intcode::IntMachine;
 use crate::parsers::parse_intmachine_input;
@@ -82,7 +83,7 @@ impl DaySolver for Day7 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> anyhow::Result<Box<dyn ToString>> {
         let memory = parse_intmachine_input(input)?;
@@ -1,12 +1,17 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
-use crate::parsers::single_input_line;
+use crate::parsers::{Digits, single_input_line};
+use crate::shared::board::Grid;
+use crate::shared::ocr::read_letters;
 use anyhow::Context;
-use itertools::Itertools;
+use itertools::{Itertools, process_results};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::io::BufRead;
 use std::str::FromStr;
 
 type Pixel = u8;
+const WHITE: Pixel = 1;
 const TRANSPARENT: Pixel = 2;
 
 struct Layer<const WIDTH: usize, const HEIGHT: usize>([[Pixel; WIDTH]; HEIGHT]);
@@ -69,6 +74,16 @@ impl<const WIDTH: usize, const HEIGHT: usize> Layer<WIDTH, HEIGHT> {
 
         Self(layer)
     }
+
+    fn as_grid(&self) -> Grid<bool> {
+        let rows = self
+            .0
+            .iter()
+            .map(|row| row.iter().map(|&pixel| pixel == WHITE).collect_vec())
+            .collect_vec();
+
+        Grid::new(rows).expect("Layer rows have the same width")
+    }
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Display for Layer<WIDTH, HEIGHT> {
@@ -91,6 +106,22 @@ struct Image<const WIDTH: usize, const HEIGHT: usize> {
     layers: Vec<Layer<WIDTH, HEIGHT>>,
 }
 
+impl<const WIDTH: usize, const HEIGHT: usize> Image<WIDTH, HEIGHT> {
+    fn from_pixels(pixels: impl Iterator<Item = Pixel>) -> anyhow::Result<Self> {
+        let mut digit_iter = pixels.peekable();
+        let layers: Vec<_> = std::iter::from_fn(|| {
+            if digit_iter.peek().is_some() {
+                Some(Layer::parse_layer(&mut digit_iter.by_ref()))
+            } else {
+                None
+            }
+        })
+        .try_collect()?;
+
+        Ok(Self { layers })
+    }
+}
+
 impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Image<WIDTH, HEIGHT> {
     type Err = anyhow::Error;
 
@@ -104,17 +135,7 @@ impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Image<WIDTH, HEIGHT> {
             })
             .try_collect()?;
 
-        let mut digit_iter = digits.into_iter().peekable();
-        let layers: Vec<_> = std::iter::from_fn(|| {
-            if digit_iter.peek().is_some() {
-                Some(Layer::parse_layer(&mut digit_iter.by_ref()))
-            } else {
-                None
-            }
-        })
-        .try_collect()?;
-
-        Ok(Self { layers })
+        Self::from_pixels(digits.into_iter())
     }
 }
 
@@ -130,31 +151,21 @@ impl<const WIDTH: usize, const HEIGHT: usize> Display for Image<WIDTH, HEIGHT> {
 
 pub(crate) struct Day8 {}
 
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
 impl Day8 {
     pub(crate) fn new() -> Self {
         Self {}
     }
 
-    fn parse_input<const WIDTH: usize, const HEIGHT: usize>(
-        lines: &[&str],
-    ) -> anyhow::Result<Image<WIDTH, HEIGHT>> {
+    fn parse_input(lines: &[&str]) -> anyhow::Result<Image<WIDTH, HEIGHT>> {
         let line = single_input_line(lines)?;
 
         Image::from_str(line)
     }
-}
-
-impl DaySolver for Day8 {
-    fn solve_part(
-        &self,
-        part: DayPart,
-        _example: bool,
-        input: &[&str],
-    ) -> Result<Box<dyn ToString>, anyhow::Error> {
-        const WIDTH: usize = 25;
-        const HEIGHT: usize = 6;
-        let image: Image<WIDTH, HEIGHT> = Self::parse_input(input)?;
 
+    fn solve(part: DayPart, image: Image<WIDTH, HEIGHT>) -> anyhow::Result<Box<dyn ToString>> {
         match part {
             DayPart::Part1 => {
                 let (_layer, pixel_counter) = image
@@ -177,9 +188,54 @@ impl DaySolver for Day8 {
                     .into_iter()
                     .reduce(|front, back| Layer::render(&front, &back))
                     .expect("At least one layer");
+                let letters = read_letters(&render.as_grid())?;
 
-                Ok(Box::new(render))
+                Ok(Box::new(letters))
             }
         }
     }
 }
+
+impl DaySolver for Day8 {
+    fn solve_part(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &[&str],
+    ) -> Result<Box<dyn ToString>, anyhow::Error> {
+        Self::solve(part, Self::parse_input(input)?)
+    }
+
+    fn solve_stream(
+        &self,
+        part: DayPart,
+        _params: &Params,
+        input: &mut dyn BufRead,
+    ) -> Result<Box<dyn ToString>, anyhow::Error> {
+        let image = process_results(Digits::new(input), |pixels| Image::from_pixels(pixels))??;
+
+        Self::solve(part, image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream() {
+        // The second layer has no zero, and 75 ones and twos each
+        let pixels = WIDTH * HEIGHT;
+        let input = format!("{}{}", "0".repeat(pixels), "12".repeat(pixels / 2));
+        let stream = format!("{input}\n");
+        let params = Params::new();
+
+        let solved = Day8::new().solve_part(DayPart::Part1, &params, &[&input]);
+        assert_eq!(solved.unwrap().to_string(), "5625");
+
+        let streamed = Day8::new()
+            .solve_stream(DayPart::Part1, &params, &mut stream.as_bytes())
+            .unwrap();
+        assert_eq!(streamed.to_string(), "5625");
+    }
+}
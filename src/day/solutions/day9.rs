@@ -1,3 +1,4 @@
+use crate::day::params::Params;
 use crate::day::{DayPart, DaySolver};
 use crate::intcode::IntMachine;
 use crate::parsers::parse_intmachine_input;
@@ -16,7 +17,7 @@ impl DaySolver for Day9 {
     fn solve_part(
         &self,
         part: DayPart,
-        _example: bool,
+        _params: &Params,
         input: &[&str],
     ) -> Result<Box<dyn ToString>, Error> {
         let mem = parse_intmachine_input(input)?;
@@ -0,0 +1,237 @@
+use crate::shared::board::{Board, Grid};
+use crate::shared::coord::Coord;
+use anyhow::Context;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const GRAY: Self = Self(96, 96, 96);
+    pub const RED: Self = Self(220, 50, 47);
+    pub const GREEN: Self = Self(60, 180, 75);
+    pub const BLUE: Self = Self(38, 139, 210);
+    pub const YELLOW: Self = Self(230, 190, 40);
+
+    /// Perceived brightness, as used for grayscale output
+    pub fn luma(self) -> u8 {
+        let Self(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+
+    fn hex(self) -> String {
+        let Self(r, g, b) = self;
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary colour PPM (`P6`)
+    Ppm,
+    /// Binary grayscale PGM (`P5`)
+    Pgm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("ppm") => Ok(Self::Ppm),
+            Some("pgm") => Ok(Self::Pgm),
+            Some("png") => Ok(Self::Png),
+            Some("svg") => Ok(Self::Svg),
+            _ => Err(anyhow::anyhow!(
+                "Unknown image format for {}, expected .ppm, .pgm, .png or .svg",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Board rendered to one colour per cell
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Picture {
+    pixels: Grid<Rgb>,
+}
+
+impl Picture {
+    /// Colours every cell within the board's bounds, so sparse boards show their default value
+    /// where nothing was written
+    pub(crate) fn from_board<T: Clone, B: Board<T>>(
+        board: &B,
+        color: impl Fn(Coord, &T) -> Rgb,
+    ) -> Result<Self, anyhow::Error> {
+        let (top_left, bottom_right) = board.bounds().context("Cannot draw an empty board")?;
+
+        let width = top_left.x().abs_diff(bottom_right.x()) as usize + 1;
+        let height = top_left.y().abs_diff(bottom_right.y()) as usize + 1;
+
+        let pixels = Grid::from_fn(width, height, |offset| {
+            let coord = top_left + offset;
+            board
+                .read(coord)
+                .map(|value| color(coord, value))
+                .unwrap_or_default()
+        });
+
+        Ok(Self { pixels })
+    }
+
+    pub(crate) fn pixels(&self) -> &Grid<Rgb> {
+        &self.pixels
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    /// Blows every cell up into a `factor` by `factor` square
+    pub fn scaled(&self, factor: usize) -> Self {
+        let pixels = Grid::from_fn(self.width() * factor, self.height() * factor, |coord| {
+            self.pixels[(coord.x() as usize / factor, coord.y() as usize / factor)]
+        });
+
+        Self { pixels }
+    }
+
+    /// Writes to a file, picking the format from its extension
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let format = ImageFormat::from_path(path)?;
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        let mut writer = BufWriter::new(file);
+        self.write(format, &mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn write(&self, format: ImageFormat, writer: &mut dyn Write) -> Result<(), anyhow::Error> {
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            ImageFormat::Pgm => self.write_pgm(writer),
+            ImageFormat::Png => self.write_png(writer),
+            ImageFormat::Svg => self.write_svg(writer),
+        }
+    }
+
+    fn write_ppm(&self, writer: &mut dyn Write) -> Result<(), anyhow::Error> {
+        write!(writer, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        writer.write_all(&self.rgb_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_pgm(&self, writer: &mut dyn Write) -> Result<(), anyhow::Error> {
+        write!(writer, "P5\n{} {}\n255\n", self.width(), self.height())?;
+        let bytes: Vec<u8> = self
+            .pixels
+            .rows()
+            .flatten()
+            .map(|pixel| pixel.luma())
+            .collect();
+        writer.write_all(&bytes)?;
+
+        Ok(())
+    }
+
+    fn write_png(&self, writer: &mut dyn Write) -> Result<(), anyhow::Error> {
+        let mut encoder = png::Encoder::new(writer, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&self.rgb_bytes())?;
+        png_writer.finish()?;
+
+        Ok(())
+    }
+
+    /// One rectangle per horizontal run of equal colour, keeping the file small
+    fn write_svg(&self, writer: &mut dyn Write) -> Result<(), anyhow::Error> {
+        let (width, height) = (self.width(), self.height());
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+        )?;
+
+        for (y, row) in self.pixels.rows().enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|left, right| left == right) {
+                writeln!(
+                    writer,
+                    r#"<rect x="{x}" y="{y}" width="{}" height="1" fill="{}"/>"#,
+                    run.len(),
+                    run[0].hex()
+                )?;
+                x += run.len();
+            }
+        }
+
+        writeln!(writer, "</svg>")?;
+
+        Ok(())
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .rows()
+            .flatten()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::board::HashBoard;
+
+    #[test]
+    fn export_formats() {
+        let mut board = HashBoard::new(false);
+        board.write(Coord::new(-1, 0), true).unwrap();
+        board.write(Coord::new(1, 1), false).unwrap();
+
+        let picture = Picture::from_board(&board, |_coord, &lit| {
+            if lit { Rgb::WHITE } else { Rgb::BLACK }
+        })
+        .unwrap();
+        assert_eq!((picture.width(), picture.height()), (3, 2));
+
+        let mut ppm = Vec::new();
+        picture.write(ImageFormat::Ppm, &mut ppm).unwrap();
+        assert_eq!(&ppm[..11], b"P6\n3 2\n255\n");
+        assert_eq!(&ppm[11..17], &[255, 255, 255, 0, 0, 0]);
+
+        let mut pgm = Vec::new();
+        picture.scaled(2).write(ImageFormat::Pgm, &mut pgm).unwrap();
+        assert_eq!(&pgm[..11], b"P5\n6 4\n255\n");
+        assert_eq!(pgm.len(), 11 + 6 * 4);
+
+        let mut png = Vec::new();
+        picture.write(ImageFormat::Png, &mut png).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+
+        let mut svg = Vec::new();
+        picture.write(ImageFormat::Svg, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+}
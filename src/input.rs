@@ -0,0 +1,63 @@
+use crate::parsers::{open_file, read_all};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+mod data_root;
+
+pub use data_root::{DATA_DIR_VAR, data_root};
+
+pub fn day_dir(data_root: &Path, day: u32) -> PathBuf {
+    data_root.join("days").join(format!("day{day}"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Real,
+    /// `example.txt`, or `example{name}.txt` for a named example
+    Example(Option<String>),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Input named after a test scenario: `real`, `example` or `example{name}`
+    pub fn from_scenario(scenario: &str) -> Result<Self, anyhow::Error> {
+        match scenario {
+            "real" => Ok(Self::Real),
+            "example" => Ok(Self::Example(None)),
+            _ => match scenario.strip_prefix("example") {
+                Some(name) => Ok(Self::Example(Some(name.to_owned()))),
+                None => Err(anyhow::anyhow!("Unknown scenario {scenario}")),
+            },
+        }
+    }
+
+    pub fn is_example(&self) -> bool {
+        matches!(self, Self::Example(_))
+    }
+
+    pub fn file_path(&self, data_root: &Path, day: u32) -> Option<PathBuf> {
+        let input_dir = day_dir(data_root, day).join("input");
+
+        match self {
+            Self::Real => Some(input_dir.join("real.txt")),
+            Self::Example(None) => Some(input_dir.join("example.txt")),
+            Self::Example(Some(name)) => Some(input_dir.join(format!("example{name}.txt"))),
+            Self::Path(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    /// Open the input for streaming
+    pub fn open(&self, data_root: &Path, day: u32) -> Result<Box<dyn BufRead>, anyhow::Error> {
+        match self.file_path(data_root, day) {
+            Some(path) => open_file(&path),
+            None => Ok(Box::new(std::io::stdin().lock())),
+        }
+    }
+
+    /// Read the whole input, to be split into borrowed lines
+    pub fn read(&self, data_root: &Path, day: u32) -> Result<String, anyhow::Error> {
+        read_all(self.open(data_root, day)?)
+    }
+}
@@ -0,0 +1,24 @@
+//! Data root lookup, shared with the build script that generates the integration tests
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding where the `days/` data directory lives
+pub const DATA_DIR_VAR: &str = "AOC2019_DATA_DIR";
+
+/// Resolve the data root: an explicit path wins, then the environment, then `./data` if present,
+/// falling back to the `data/` directory next to the crate manifest
+pub fn data_root(explicit: Option<&Path>) -> PathBuf {
+    if let Some(path) = explicit {
+        return path.to_path_buf();
+    }
+
+    if let Some(path) = std::env::var_os(DATA_DIR_VAR) {
+        return PathBuf::from(path);
+    }
+
+    let local = PathBuf::from("data");
+    if local.is_dir() {
+        local
+    } else {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data")
+    }
+}
@@ -1,6 +1,9 @@
 pub mod args;
 pub mod day;
+pub mod export;
+pub mod input;
 mod intcode;
 pub mod parsers;
 mod shared;
 mod types;
+pub mod visualize;
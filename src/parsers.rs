@@ -4,9 +4,25 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+mod char_map;
+mod combinators;
+mod stream;
+
+pub(crate) use char_map::char_map;
+pub(crate) use combinators::{
+    ParseError, ParseResult, capture, char_grid, comma_separated, digits, lines, parse_lines,
+    record, separated, single_line, value,
+};
+pub(crate) use stream::{Digits, stream_lines};
+pub use stream::{open_file, read_all};
+
 pub fn parse_file(file: &Path) -> Result<Vec<String>, anyhow::Error> {
     let file = File::open(file).map_err(|e| anyhow!("Failed to open file {file:?}: {e}"))?;
-    let reader = BufReader::new(file);
+
+    parse_reader(BufReader::new(file))
+}
+
+pub fn parse_reader(reader: impl BufRead) -> Result<Vec<String>, anyhow::Error> {
     let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
 
     Ok(lines)
@@ -21,12 +37,7 @@ pub fn single_input_line<'a>(lines: &[&'a str]) -> Result<&'a str, anyhow::Error
 }
 
 pub fn parse_intmachine_input(lines: &[&str]) -> Result<Vec<IntCell>, anyhow::Error> {
-    match lines {
-        [] => Err(anyhow::anyhow!("No input lines")),
-        [line] => line
-            .split(',')
-            .map(|value| value.parse::<IntCell>().map_err(|e| e.into()))
-            .collect(),
-        [..] => Err(anyhow::anyhow!("Too many input lines")),
-    }
+    let memory = single_line(lines, comma_separated)?;
+
+    Ok(memory)
 }
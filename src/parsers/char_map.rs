@@ -0,0 +1,61 @@
+use crate::parsers::{ParseError, ParseResult, char_grid};
+use crate::shared::board::Grid;
+use crate::shared::coord::Coord;
+use std::collections::BTreeMap;
+
+/// Character map parsed into a grid, with the positions of its marker characters
+#[derive(Debug)]
+pub(crate) struct CharMap<T> {
+    pub(crate) grid: Grid<T>,
+    markers: BTreeMap<char, Vec<Coord>>,
+}
+
+impl<T> CharMap<T> {
+    /// Every position of a marker, in reading order
+    pub(crate) fn positions(&self, marker: char) -> &[Coord] {
+        self.markers.get(&marker).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Position of a marker that may appear at most once
+    pub(crate) fn optional_marker(&self, marker: char) -> ParseResult<Option<Coord>> {
+        match self.positions(marker) {
+            [] => Ok(None),
+            [coord] => Ok(Some(*coord)),
+            [_, repeated, ..] => Err(ParseError::new(
+                repeated.y() as usize + 1,
+                repeated.x() as usize + 1,
+                format!("marker {marker:?} appears more than once"),
+            )),
+        }
+    }
+
+    /// Position of a marker that must appear exactly once
+    pub(crate) fn marker(&self, marker: char) -> ParseResult<Coord> {
+        self.optional_marker(marker)?
+            .ok_or_else(|| ParseError::new(1, 1, format!("marker {marker:?} not found")))
+    }
+}
+
+/// Parse a character map into a grid, recording where the characters accepted by `is_marker`
+/// are. Marker cells are still mapped into the grid, usually as open floor.
+pub(crate) fn char_map<T: Clone>(
+    input: &[&str],
+    is_marker: impl Fn(char) -> bool,
+    mapper: impl Fn(char) -> Option<T>,
+) -> ParseResult<CharMap<T>> {
+    let grid = char_grid(input, mapper)?;
+
+    let mut markers: BTreeMap<char, Vec<Coord>> = BTreeMap::new();
+    for (y, line) in input.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if is_marker(c) {
+                markers
+                    .entry(c)
+                    .or_default()
+                    .push(Coord::new(x as i32, y as i32));
+            }
+        }
+    }
+
+    Ok(CharMap { grid, markers })
+}
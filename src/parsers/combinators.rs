@@ -0,0 +1,281 @@
+use crate::shared::board::Grid;
+use regex::{Captures, Regex};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Parsing failure located at a 1-based line and column of the input
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}, column {column}: {message}")]
+pub(crate) struct ParseError {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Display) -> Self {
+        Self {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    /// Error within a single line, to be located by the caller with [`ParseError::offset`]
+    pub(crate) fn at_column(column: usize, message: impl Display) -> Self {
+        Self::new(1, column, message)
+    }
+
+    /// Relocate an error from a sub-input starting at `line_offset` lines and, when the error is
+    /// on its first line, `column_offset` columns into the enclosing input
+    pub(crate) fn offset(self, line_offset: usize, column_offset: usize) -> Self {
+        let column = if self.line == 1 {
+            self.column + column_offset
+        } else {
+            self.column
+        };
+
+        Self::new(self.line + line_offset, column, self.message)
+    }
+}
+
+pub(crate) type ParseResult<T> = Result<T, ParseError>;
+
+/// Parse a whole token with [`FromStr`]
+pub(crate) fn value<T: FromStr>(token: &str) -> ParseResult<T>
+where
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| ParseError::at_column(1, format!("{e} (in {token:?})")))
+}
+
+/// Parse each line, locating errors by line number
+pub(crate) fn lines<T>(
+    input: &[&str],
+    parser: impl Fn(&str) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parser(line).map_err(|e| e.offset(idx, 0)))
+        .collect()
+}
+
+/// Parse each line with [`FromStr`]
+pub(crate) fn parse_lines<T: FromStr>(input: &[&str]) -> ParseResult<Vec<T>>
+where
+    T::Err: Display,
+{
+    lines(input, value)
+}
+
+/// Parse the only line of the input
+pub(crate) fn single_line<T>(
+    input: &[&str],
+    parser: impl Fn(&str) -> ParseResult<T>,
+) -> ParseResult<T> {
+    match input {
+        [line] => parser(line),
+        [] => Err(ParseError::new(1, 1, "no lines in input")),
+        [_, extra, ..] => Err(ParseError::new(
+            2,
+            1,
+            format!("expected a single line, found {} ({extra:?})", input.len()),
+        )),
+    }
+}
+
+/// Parse the items of a line split by `separator`, locating errors by column
+pub(crate) fn separated<T>(
+    line: &str,
+    separator: &str,
+    parser: impl Fn(&str) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut column = 0;
+
+    line.split(separator)
+        .map(|item| {
+            let result = parser(item).map_err(|e| e.offset(0, column));
+            column += item.chars().count() + separator.chars().count();
+            result
+        })
+        .collect()
+}
+
+pub(crate) fn comma_separated<T: FromStr>(line: &str) -> ParseResult<Vec<T>>
+where
+    T::Err: Display,
+{
+    separated(line, ",", value)
+}
+
+/// Parse every character of a line as a decimal digit
+pub(crate) fn digits(line: &str) -> ParseResult<Vec<u8>> {
+    line.chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            c.to_digit(10)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| ParseError::at_column(idx + 1, format!("invalid digit {c:?}")))
+        })
+        .collect()
+}
+
+/// Parse a rectangular character map, mapping each character to a cell
+pub(crate) fn char_grid<T: Clone>(
+    input: &[&str],
+    mapper: impl Fn(char) -> Option<T>,
+) -> ParseResult<Grid<T>> {
+    let rows = input
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(col, c)| {
+                    mapper(c).ok_or_else(|| {
+                        ParseError::new(row + 1, col + 1, format!("unexpected character {c:?}"))
+                    })
+                })
+                .collect::<ParseResult<Vec<_>>>()
+        })
+        .collect::<ParseResult<Vec<_>>>()?;
+
+    if let Some((row, width)) = rows
+        .iter()
+        .map(Vec::len)
+        .enumerate()
+        .find(|&(_row, width)| rows.first().is_some_and(|first| first.len() != width))
+    {
+        return Err(ParseError::new(
+            row + 1,
+            width.min(rows[0].len()) + 1,
+            format!("row has {width} cells, expected {}", rows[0].len()),
+        ));
+    }
+
+    Ok(Grid::new(rows).expect("Rows have the same width"))
+}
+
+/// Blank-line separated group of lines, remembering where it starts
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Block<'a, 'b> {
+    pub(crate) first_line: usize,
+    pub(crate) lines: &'b [&'a str],
+}
+
+/// Split the input on blank lines, skipping empty blocks
+#[allow(dead_code)]
+pub(crate) fn blocks<'a, 'b>(input: &'b [&'a str]) -> Vec<Block<'a, 'b>> {
+    let mut result = Vec::new();
+    let mut start = 0;
+
+    for (idx, line) in input.iter().chain(std::iter::once(&"")).enumerate() {
+        if line.trim().is_empty() {
+            if idx > start {
+                result.push(Block {
+                    first_line: start + 1,
+                    lines: &input[start..idx],
+                });
+            }
+            start = idx + 1;
+        }
+    }
+
+    result
+}
+
+/// Parse each blank-line separated block, locating errors within the whole input
+#[allow(dead_code)]
+pub(crate) fn parse_blocks<T>(
+    input: &[&str],
+    parser: impl Fn(&[&str]) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    blocks(input)
+        .into_iter()
+        .map(|block| parser(block.lines).map_err(|e| e.offset(block.first_line - 1, 0)))
+        .collect()
+}
+
+/// Match a whole line against a regex describing a record, rejecting matches that leave part of
+/// the line out
+pub(crate) fn record<'l>(pattern: &Regex, line: &'l str) -> ParseResult<Captures<'l>> {
+    let captures = pattern
+        .captures(line)
+        .ok_or_else(|| ParseError::at_column(1, format!("expected {pattern}, found {line:?}")))?;
+
+    let matched = captures.get_match();
+    if matched.start() > 0 {
+        return Err(ParseError::at_column(
+            1,
+            format!("unexpected {:?} before {pattern}", &line[..matched.start()]),
+        ));
+    }
+    if matched.end() < line.len() {
+        return Err(ParseError::at_column(
+            line[..matched.end()].chars().count() + 1,
+            format!("unexpected {:?} after {pattern}", &line[matched.end()..]),
+        ));
+    }
+
+    Ok(captures)
+}
+
+/// Parse a named capture of a [`record`], locating errors at the capture within the line
+pub(crate) fn capture<T: FromStr>(captures: &Captures, name: &str) -> ParseResult<T>
+where
+    T::Err: Display,
+{
+    let group = captures
+        .name(name)
+        .ok_or_else(|| ParseError::at_column(1, format!("missing field {name}")))?;
+    // Records span their whole line, so the match starts the line
+    let line = captures.get_match().as_str();
+    let column = line[..group.start()].chars().count();
+
+    value(group.as_str()).map_err(|e| e.offset(0, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn locate_errors() {
+        let error = lines(&["1,2,3", "4,x,6"], comma_separated::<u32>).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let pattern = Regex::new(r"^(?<name>\w+) = (?<count>\S+)$").unwrap();
+        let error = record(&pattern, "abc = 1x")
+            .and_then(|captures| capture::<u32>(&captures, "count"))
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+
+        let unanchored = Regex::new(r"(?<name>\w+) = (?<count>\d+)").unwrap();
+        let error = record(&unanchored, "  abc = 12").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = record(&unanchored, "abc = 12 apples").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+
+        let error = parse_blocks(&["1", "2", "", "", "3", "y"], parse_lines::<u32>).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 1));
+
+        let error = char_grid(&["#.#", "#.", "..."], |c| Some(c == '#')).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn split_blocks() {
+        let input = ["a", "b", "", "c", "", ""];
+        let blocks = blocks(&input)
+            .into_iter()
+            .map(|block| (block.first_line, block.lines.iter().join("")))
+            .collect_vec();
+
+        assert_eq!(blocks, vec![(1, "ab".to_owned()), (4, "c".to_owned())]);
+    }
+}
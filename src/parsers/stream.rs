@@ -0,0 +1,141 @@
+use crate::parsers::{ParseError, ParseResult};
+use anyhow::anyhow;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+/// Files at least this big are memory-mapped instead of read through a buffer
+const MMAP_THRESHOLD: u64 = 1 << 20;
+
+/// Open a file for streaming, memory-mapping it when it is large
+pub fn open_file(path: &Path) -> Result<Box<dyn BufRead>, anyhow::Error> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open file {path:?}: {e}"))?;
+
+    if file.metadata()?.len() < MMAP_THRESHOLD {
+        return Ok(Box::new(BufReader::new(file)));
+    }
+
+    // SAFETY: inputs are not expected to be modified while a puzzle is being solved
+    let mmap = unsafe { Mmap::map(&file) }.map_err(|e| anyhow!("Failed to map {path:?}: {e}"))?;
+
+    Ok(Box::new(Cursor::new(mmap)))
+}
+
+/// Read a whole input once, so that its lines can be borrowed without copying them again
+pub fn read_all(mut reader: impl Read) -> Result<String, anyhow::Error> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+/// Parse each line of a streamed input lazily, locating errors by line number
+pub(crate) fn stream_lines<T>(
+    reader: impl BufRead,
+    parser: impl Fn(&str) -> ParseResult<T>,
+) -> impl Iterator<Item = ParseResult<T>> {
+    reader.lines().enumerate().map(move |(idx, line)| {
+        line.map_err(|e| ParseError::at_column(1, e))
+            .and_then(|line| parser(&line))
+            .map_err(|e| e.offset(idx, 0))
+    })
+}
+
+/// Decimal digits of a single-line input, read one byte at a time
+pub(crate) struct Digits<R> {
+    bytes: std::io::Bytes<R>,
+    column: usize,
+    finished: bool,
+}
+
+impl<R: BufRead> Digits<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            bytes: reader.bytes(),
+            column: 0,
+            finished: false,
+        }
+    }
+
+    /// Only whitespace may follow the line of digits
+    fn check_trailing(&mut self) -> ParseResult<()> {
+        for byte in self.bytes.by_ref() {
+            let byte = byte.map_err(|e| ParseError::new(2, 1, e))?;
+            if !byte.is_ascii_whitespace() {
+                return Err(ParseError::new(2, 1, "expected a single line of digits"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for Digits<R> {
+    type Item = ParseResult<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        self.column += 1;
+        let byte = match self.bytes.next() {
+            None => {
+                self.finished = true;
+                return None;
+            }
+            Some(Err(e)) => {
+                self.finished = true;
+                return Some(Err(ParseError::new(1, self.column, e)));
+            }
+            Some(Ok(byte)) => byte,
+        };
+
+        match byte {
+            b'0'..=b'9' => Some(Ok(byte - b'0')),
+            b'\r' | b'\n' => {
+                self.finished = true;
+                self.check_trailing().err().map(Err)
+            }
+            other => {
+                self.finished = true;
+                Some(Err(ParseError::at_column(
+                    self.column,
+                    format!("invalid digit {:?}", other as char),
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::value;
+    use itertools::Itertools;
+
+    #[test]
+    fn digits() {
+        let digits: ParseResult<Vec<u8>> = Digits::new("0123\n".as_bytes()).collect();
+        assert_eq!(digits.unwrap(), vec![0, 1, 2, 3]);
+
+        let error = Digits::new("01x3".as_bytes()).try_collect::<_, Vec<_>, _>();
+        assert_eq!(error.unwrap_err().column, 3);
+
+        let error = Digits::new("0123\n45".as_bytes()).try_collect::<_, Vec<_>, _>();
+        assert_eq!(error.unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn lines() {
+        let parsed = stream_lines("1\n2\n".as_bytes(), value::<u32>).try_collect::<_, Vec<_>, _>();
+        assert_eq!(parsed.unwrap(), vec![1, 2]);
+
+        let mut parsed = stream_lines("1\n2\nx\n4\n".as_bytes(), value::<u32>);
+        assert!(parsed.next().unwrap().is_ok());
+        assert!(parsed.next().unwrap().is_ok());
+        let error = parsed.next().unwrap().unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+}
@@ -1,4 +1,14 @@
 pub(crate) mod board;
 pub(crate) mod coord;
+pub(crate) mod cycle;
+pub(crate) mod geometry;
+pub(crate) mod graph;
+// No puzzle solved so far walks a hex grid
+#[allow(dead_code)]
+pub(crate) mod hex;
+pub(crate) mod nbody;
 pub(crate) mod vect3;
-pub(crate) mod a_star;
+pub(crate) mod search;
+pub(crate) mod ocr;
+pub(crate) mod segment;
+pub(crate) mod point;
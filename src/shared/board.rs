@@ -1,5 +1,7 @@
-use std::fmt::Debug;
 use crate::shared::coord::Coord;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
 
 mod grid;
 mod hash_board;
@@ -12,5 +14,160 @@ pub(crate) trait Board<T: Clone> : Debug {
 
     fn write(&mut self, coord: Coord, value: T) -> Result<(), anyhow::Error>;
 
-    fn elements(&self) -> Vec<(Coord, T)>;
+    /// Borrowing iterator over the stored cells
+    fn iter(&self) -> Box<dyn Iterator<Item = (Coord, &T)> + '_>;
+
+    /// Top-left and bottom-right corners of the stored cells, both inclusive
+    fn bounds(&self) -> Option<(Coord, Coord)>;
+
+    fn elements(&self) -> Vec<(Coord, T)> {
+        self.iter()
+            .map(|(coord, value)| (coord, value.clone()))
+            .collect_vec()
+    }
+
+    fn find(&self, value: &T) -> Option<Coord>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(coord, element)| (element == value).then_some(coord))
+    }
+}
+
+/// Search and neighbourhood helpers for every board, trait objects included
+pub(crate) trait BoardExt<T: Clone>: Board<T> {
+    fn positions<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coord> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(coord, element)| (element == value).then_some(coord))
+    }
+
+    fn neighbours4<'a>(&'a self, coord: Coord) -> impl Iterator<Item = (Coord, &'a T)>
+    where
+        T: 'a,
+    {
+        coord
+            .cross()
+            .filter_map(|neighbour| self.read(neighbour).ok().map(|value| (neighbour, value)))
+    }
+
+    #[allow(dead_code)]
+    fn neighbours8<'a>(&'a self, coord: Coord) -> impl Iterator<Item = (Coord, &'a T)>
+    where
+        T: 'a,
+    {
+        coord
+            .ring()
+            .filter_map(|neighbour| self.read(neighbour).ok().map(|value| (neighbour, value)))
+    }
+
+    /// Lazy breadth-first walk from `start`, yielding each reached cell with its distance.
+    /// Cells that are not passable are reached, but not walked through.
+    fn bfs<F>(&self, start: Coord, passable: F) -> Bfs<'_, T, Self, F>
+    where
+        F: Fn(Coord, &T) -> bool,
+    {
+        Bfs {
+            board: self,
+            passable,
+            queue: VecDeque::from([(start, 0)]),
+            seen: HashSet::from([start]),
+            _value: Default::default(),
+        }
+    }
+
+    /// Distance to every passable cell reachable from `start`
+    fn bfs_distances(
+        &self,
+        start: Coord,
+        passable: impl Fn(Coord, &T) -> bool,
+    ) -> HashMap<Coord, u32> {
+        self.bfs(start, &passable)
+            .filter(|&(coord, _distance)| {
+                self.read(coord)
+                    .is_ok_and(|value| passable(coord, value))
+            })
+            .collect()
+    }
+
+    /// Passable cells reachable from `start`
+    #[allow(dead_code)]
+    fn flood_fill(&self, start: Coord, passable: impl Fn(Coord, &T) -> bool) -> HashSet<Coord> {
+        self.bfs_distances(start, passable).into_keys().collect()
+    }
+}
+
+impl<T: Clone, B: Board<T> + ?Sized> BoardExt<T> for B {}
+
+pub(crate) struct Bfs<'a, T, B: ?Sized, F> {
+    board: &'a B,
+    passable: F,
+    queue: VecDeque<(Coord, u32)>,
+    seen: HashSet<Coord>,
+    _value: std::marker::PhantomData<T>,
+}
+
+impl<'a, T, B, F> Iterator for Bfs<'a, T, B, F>
+where
+    T: Clone,
+    B: Board<T> + ?Sized,
+    F: Fn(Coord, &T) -> bool,
+{
+    type Item = (Coord, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (coord, distance) = self.queue.pop_front()?;
+
+        if self
+            .board
+            .read(coord)
+            .is_ok_and(|value| (self.passable)(coord, value))
+        {
+            for (neighbour, _value) in self.board.neighbours4(coord) {
+                if self.seen.insert(neighbour) {
+                    self.queue.push_back((neighbour, distance + 1));
+                }
+            }
+        }
+
+        Some((coord, distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::char_map;
+
+    #[test]
+    fn search_helpers() {
+        #[rustfmt::skip]
+        let map = char_map(&[
+            "S.#..",
+            ".##..",
+            "...#.",
+        ], |c| c == 'S', |c| Some(c != '#')).unwrap();
+        let start = map.marker('S').unwrap();
+        let grid = map.grid;
+
+        let distances = grid.bfs_distances(start, |_coord, &open| open);
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&Coord::new(2, 2)], 4);
+
+        let walls = grid.positions(&false).collect_vec();
+        assert_eq!(walls.len(), 4);
+        assert_eq!(grid.find(&false), Some(Coord::new(2, 0)));
+
+        assert_eq!(grid.neighbours4(Coord::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Coord::new(1, 1)).count(), 8);
+        assert_eq!(grid.flood_fill(Coord::new(4, 0), |_coord, &open| open).len(), 5);
+        assert_eq!(grid.bounds(), Some((Coord::new(0, 0), Coord::new(4, 2))));
+
+        let board: &dyn Board<bool> = &grid;
+        assert_eq!(board.positions(&false).count(), 4);
+        assert_eq!(board.bfs_distances(start, |_coord, &open| open), distances);
+    }
 }
@@ -2,10 +2,13 @@ use crate::shared::board::Board;
 use crate::shared::coord::Coord;
 use itertools::Itertools;
 use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct Grid<T> {
-    rows: Vec<Vec<T>>,
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -22,109 +25,337 @@ pub(crate) enum GridAccessError {
 
 impl<T: Clone> Grid<T> {
     pub(crate) fn new(rows: Vec<Vec<T>>) -> Result<Self, anyhow::Error> {
-        match rows.iter().map(Vec::len).all_equal_value() {
-            Ok(_) | Err(None) => Ok(Self { rows }),
-            Err(Some((first, second))) => Err(anyhow::anyhow!(
-                "Mismatch on board row sizes, found {first} and {second}"
-            )),
-        }
+        let width = match rows.iter().map(Vec::len).all_equal_value() {
+            Ok(width) => width,
+            Err(None) => 0,
+            Err(Some((first, second))) => {
+                return Err(anyhow::anyhow!(
+                    "Mismatch on board row sizes, found {first} and {second}"
+                ));
+            }
+        };
+        let height = rows.len();
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
     }
 
     pub(crate) fn with_value(width: usize, height: usize, value: T) -> Self {
-        Self::new(vec![vec![value; width]; height]).expect("Build an empty board")
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn width(&self) -> usize {
-        self.rows.first().map(Vec::len).unwrap_or_default()
+        self.width
     }
 
-    #[allow(dead_code)]
     pub(crate) fn height(&self) -> usize {
-        self.rows.len()
+        self.height
     }
 
     pub(crate) fn coord_to_col_row(coord: Coord) -> Result<(usize, usize), GridAccessError> {
-        let col = usize::try_from(coord.x).map_err(|_| GridAccessError::InvalidCol(coord.x))?;
-        let row = usize::try_from(coord.y).map_err(|_| GridAccessError::InvalidRow(coord.y))?;
+        let col = usize::try_from(coord.x()).map_err(|_| GridAccessError::InvalidCol(coord.x()))?;
+        let row = usize::try_from(coord.y()).map_err(|_| GridAccessError::InvalidRow(coord.y()))?;
 
         Ok((col, row))
     }
 
-    pub(crate) fn map<O: Clone>(self, mapper: fn(T) -> O) -> Grid<O> {
-        let rows = self
-            .rows
-            .into_iter()
-            .map(|row| row.into_iter().map(mapper).collect_vec())
-            .collect_vec();
+    fn index_of(&self, coord: Coord) -> Result<usize, GridAccessError> {
+        let (col, row) = Self::coord_to_col_row(coord)?;
 
-        Grid::new(rows).expect("Map a grid into another")
-    }
-
-	#[allow(unused)]
-    pub(crate) fn map_tuples<O: Clone, F: Fn((Coord, T)) -> O + Copy>(self, mapper: F) -> Grid<O> {
-        let rows = self
-            .rows
-            .into_iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.into_iter()
-                    .enumerate()
-                    .map(|(x, element)| (Coord::new(x as i32, y as i32), element))
-	                .map(mapper)
-                    .collect_vec()
-            })
-            .collect_vec();
+        if row >= self.height {
+            return Err(GridAccessError::OutOfBoundsRow(row));
+        }
+        if col >= self.width {
+            return Err(GridAccessError::OutOfBoundsCol(col));
+        }
+
+        Ok(row * self.width + col)
+    }
+}
+
+impl<T> Grid<T> {
+    fn index_to_coord(&self, index: usize) -> Coord {
+        Coord::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        (row < self.height).then(|| &mut self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    /// Reads a cell without any bounds check
+    ///
+    /// # Safety
+    ///
+    /// `col` must be below the width and `row` below the height of the grid.
+    #[allow(dead_code)]
+    pub(crate) unsafe fn get_unchecked(&self, col: usize, row: usize) -> &T {
+        debug_assert!(col < self.width && row < self.height);
+        // SAFETY: the caller guarantees the cell is inside the grid
+        unsafe { self.cells.get_unchecked(row * self.width + col) }
+    }
+}
+
+/// Fast positional access by `(col, row)`, panicking outside the grid
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (col, row): (usize, usize)) -> &Self::Output {
+        assert!(col < self.width, "column {col} out of bounds");
+        assert!(row < self.height, "row {row} out of bounds");
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Self::Output {
+        assert!(col < self.width, "column {col} out of bounds");
+        assert!(row < self.height, "row {row} out of bounds");
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// Read-only window over a rectangular region of a grid
+#[derive(Debug)]
+pub(crate) struct SubGrid<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Coord,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> SubGrid<'_, T> {
+    #[allow(dead_code)]
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Reads a cell using coordinates relative to the window's top-left corner
+    pub(crate) fn read(&self, coord: Coord) -> Result<&T, GridAccessError> {
+        let (col, row) = Grid::<T>::coord_to_col_row(coord)?;
+
+        if row >= self.height {
+            return Err(GridAccessError::OutOfBoundsRow(row));
+        }
+        if col >= self.width {
+            return Err(GridAccessError::OutOfBoundsCol(col));
+        }
+
+        Ok(self
+            .grid
+            .cell(self.origin.x() as usize + col, self.origin.y() as usize + row))
+    }
+
+    pub(crate) fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |coord| {
+            self.read(coord).expect("Read inside the window").clone()
+        })
+    }
+}
+
+/// Image-style transformations, all of them building a new grid
+impl<T: Clone> Grid<T> {
+    pub(crate) fn from_fn(width: usize, height: usize, cell: impl Fn(Coord) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord::new(x as i32, y as i32)))
+            .map(cell)
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    fn cell(&self, col: usize, row: usize) -> &T {
+        &self[(col, row)]
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn transpose(&self) -> Self {
+        Self::from_fn(self.height(), self.width(), |coord| {
+            self.cell(coord.y() as usize, coord.x() as usize).clone()
+        })
+    }
+
+    /// Mirrors the grid left to right
+    #[allow(dead_code)]
+    pub(crate) fn flip_horizontal(&self) -> Self {
+        let width = self.width();
+
+        Self::from_fn(width, self.height(), |coord| {
+            self.cell(width - 1 - coord.x() as usize, coord.y() as usize)
+                .clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom
+    #[allow(dead_code)]
+    pub(crate) fn flip_vertical(&self) -> Self {
+        let height = self.height();
+
+        Self::from_fn(self.width(), height, |coord| {
+            self.cell(coord.x() as usize, height - 1 - coord.y() as usize)
+                .clone()
+        })
+    }
+
+    /// Rotates clockwise by the given number of quarter turns, negative values turning counter-clockwise
+    #[allow(dead_code)]
+    pub(crate) fn rotate(&self, quarter_turns: i32) -> Self {
+        let (width, height) = (self.width(), self.height());
+
+        match quarter_turns.rem_euclid(4) {
+            0 => self.clone(),
+            1 => Self::from_fn(height, width, |coord| {
+                self.cell(coord.y() as usize, height - 1 - coord.x() as usize)
+                    .clone()
+            }),
+            2 => self.flip_horizontal().flip_vertical(),
+            _ => Self::from_fn(height, width, |coord| {
+                self.cell(width - 1 - coord.y() as usize, coord.x() as usize)
+                    .clone()
+            }),
+        }
+    }
+
+    /// Window of the given size whose top-left corner is at `origin`
+    pub(crate) fn subgrid(
+        &self,
+        origin: Coord,
+        width: usize,
+        height: usize,
+    ) -> Result<SubGrid<'_, T>, GridAccessError> {
+        let (col, row) = Self::coord_to_col_row(origin)?;
+
+        if row + height > self.height() {
+            return Err(GridAccessError::OutOfBoundsRow(row + height));
+        }
+        if col + width > self.width() {
+            return Err(GridAccessError::OutOfBoundsCol(col + width));
+        }
+
+        Ok(SubGrid {
+            grid: self,
+            origin,
+            width,
+            height,
+        })
+    }
+
+    /// Smallest window holding every cell that matches, along with its top-left corner
+    pub(crate) fn crop(&self, keep: impl Fn(&T) -> bool) -> Option<(Coord, Self)> {
+        let (min, max) = self
+            .cells
+            .iter()
+            .positions(keep)
+            .map(|index| (index % self.width, index / self.width))
+            .fold(
+                None,
+                |bounds: Option<((usize, usize), (usize, usize))>, (x, y)| {
+                    Some(match bounds {
+                        None => ((x, y), (x, y)),
+                        Some((min, max)) => {
+                            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+                        }
+                    })
+                },
+            )?;
+
+        let origin = Coord::new(min.0 as i32, min.1 as i32);
+        let window = self
+            .subgrid(origin, max.0 - min.0 + 1, max.1 - min.1 + 1)
+            .expect("Crop inside the grid");
 
-        Grid::new(rows).expect("Map a grid into another")
+        Some((origin, window.to_grid()))
+    }
+
+    /// Surrounds the grid with `amount` cells of `value` on every side
+    #[allow(dead_code)]
+    pub(crate) fn pad(&self, amount: usize, value: T) -> Self {
+        let mut padded =
+            Self::with_value(self.width() + 2 * amount, self.height() + 2 * amount, value);
+
+        for (y, row) in self.rows().enumerate() {
+            let padded_row = padded.row_mut(y + amount).expect("Padded grid is taller");
+            padded_row[amount..amount + row.len()].clone_from_slice(row);
+        }
+
+        padded
+    }
+
+    /// Repeats the grid `across` times horizontally and `down` times vertically
+    #[allow(dead_code)]
+    pub(crate) fn tile(&self, across: usize, down: usize) -> Self {
+        let (width, height) = (self.width(), self.height());
+
+        Self::from_fn(width * across, height * down, |coord| {
+            self.cell(coord.x() as usize % width, coord.y() as usize % height)
+                .clone()
+        })
     }
 }
 
 impl<T: Clone + Debug> Board<T> for Grid<T> {
     fn read(&self, coord: Coord) -> Result<&T, anyhow::Error> {
-        let (col, row) = Self::coord_to_col_row(coord)?;
+        let index = self.index_of(coord)?;
 
-        let row = self
-            .rows
-            .get(row)
-            .ok_or(GridAccessError::OutOfBoundsRow(row))?;
-        let element = row.get(col).ok_or(GridAccessError::OutOfBoundsCol(col))?;
-
-        Ok(element)
+        Ok(&self.cells[index])
     }
 
     fn write(&mut self, coord: Coord, value: T) -> Result<(), anyhow::Error> {
-        let (col, row) = Self::coord_to_col_row(coord)?;
+        let index = self.index_of(coord)?;
 
-        let row = self
-            .rows
-            .get_mut(row)
-            .ok_or(GridAccessError::OutOfBoundsRow(row))?;
-        let element = row
-            .get_mut(col)
-            .ok_or(GridAccessError::OutOfBoundsCol(col))?;
-
-        *element = value;
+        self.cells[index] = value;
 
         Ok(())
     }
 
-    fn elements(&self) -> Vec<(Coord, T)> {
-        self.rows
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(x, element)| (Coord::new(x as i32, y as i32), element.clone()))
-            })
-            .collect_vec()
+    fn iter(&self) -> Box<dyn Iterator<Item = (Coord, &T)> + '_> {
+        Box::new(
+            self.cells
+                .iter()
+                .enumerate()
+                .map(|(index, element)| (self.index_to_coord(index), element)),
+        )
+    }
+
+    fn bounds(&self) -> Option<(Coord, Coord)> {
+        if self.width() == 0 || self.height() == 0 {
+            return None;
+        }
+
+        let bottom_right = Coord::new(self.width() as i32 - 1, self.height() as i32 - 1);
+
+        Some((Coord::default(), bottom_right))
     }
 }
 
 impl<T: Display> Display for Grid<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.rows {
+        for row in self.rows() {
             for element in row {
                 write!(f, "{element}")?;
             }
@@ -134,3 +365,120 @@ impl<T: Display> Display for Grid<T> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::board::BoardExt;
+    use itertools::Itertools;
+
+    #[test]
+    fn transformations() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            grid.rotate(1),
+            Grid::new(vec![vec![4, 1], vec![5, 2], vec![6, 3]]).unwrap()
+        );
+        assert_eq!(grid.rotate(-1), grid.rotate(3));
+        assert_eq!(grid.rotate(2), grid.flip_vertical().flip_horizontal());
+        assert_eq!(grid.rotate(4), grid);
+        assert_eq!(grid.transpose(), grid.rotate(1).flip_horizontal());
+
+        let padded = grid.pad(1, 0);
+        assert_eq!((padded.width(), padded.height()), (5, 4));
+        assert_eq!(
+            padded.crop(|&value| value != 0),
+            Some((Coord::new(1, 1), grid.clone()))
+        );
+        assert_eq!(padded.row(1), Some([0, 1, 2, 3, 0].as_slice()));
+        assert_eq!(padded.row(4), None);
+        assert_eq!(Grid::with_value(2, 2, 0).crop(|&value| value != 0), None);
+
+        let window = grid.subgrid(Coord::new(1, 0), 2, 2).unwrap();
+        assert_eq!(*window.read(Coord::new(1, 1)).unwrap(), 6);
+        assert_eq!((window.width(), window.height()), (2, 2));
+        assert!(grid.subgrid(Coord::new(2, 0), 2, 1).is_err());
+
+        let tiled = grid.tile(2, 2);
+        assert_eq!(
+            tiled.subgrid(Coord::new(3, 2), 3, 2).unwrap().to_grid(),
+            grid
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 out of bounds")]
+    fn index_past_last_row() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid[(2, 1)], 6);
+        // SAFETY: (2, 1) is the last cell of the 3x2 grid
+        assert_eq!(unsafe { *grid.get_unchecked(2, 1) }, 6);
+
+        let _ = grid[(0, 2)];
+    }
+
+    /// Breadth-first count of the open cells reachable from the top-left corner
+    fn reachable(width: usize, height: usize, open: impl Fn(usize, usize) -> bool) -> usize {
+        let mut seen = vec![false; width * height];
+        let mut queue = std::collections::VecDeque::from([(0usize, 0usize)]);
+        seen[0] = true;
+        let mut count = 0;
+
+        while let Some((x, y)) = queue.pop_front() {
+            count += 1;
+
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if nx < width && ny < height && !seen[ny * width + nx] && open(nx, ny) {
+                    seen[ny * width + nx] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release --ignored --nocapture"]
+    fn flood_fill_benchmark() {
+        const SIZE: usize = 2000;
+        let open = |x: usize, y: usize| x % 7 != 3 || y.is_multiple_of(11);
+
+        let nested = (0..SIZE)
+            .map(|y| (0..SIZE).map(|x| open(x, y)).collect_vec())
+            .collect_vec();
+        let grid = Grid::new(nested.clone()).unwrap();
+
+        let start = std::time::Instant::now();
+        let nested_count = reachable(SIZE, SIZE, |x, y| nested[y][x]);
+        let nested_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let flat_count = reachable(SIZE, SIZE, |x, y| grid[(x, y)]);
+        let flat_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        // SAFETY: reachable only visits cells inside the SIZE x SIZE grid
+        let unchecked_count = reachable(SIZE, SIZE, |x, y| unsafe { *grid.get_unchecked(x, y) });
+        let unchecked_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let board_count = grid.flood_fill(Coord::default(), |_coord, &open| open).len();
+        let board_time = start.elapsed();
+
+        eprintln!(
+            "nested rows: {nested_time:?}, flat cells: {flat_time:?}, unchecked: {unchecked_time:?}, Board::flood_fill: {board_time:?}"
+        );
+
+        assert_eq!(nested_count, flat_count);
+        assert_eq!(flat_count, unchecked_count);
+        assert_eq!(flat_count, board_count);
+    }
+}
@@ -1,13 +1,15 @@
 use crate::shared::board::{Board, Grid};
 use crate::shared::coord::Coord;
-use itertools::Itertools;
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
 
+/// Sparse board over the whole plane, where cells never written hold the default value
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) struct HashBoard<T> {
-    pub(crate) coord_mapping: HashMap<Coord, T>,
+    coord_mapping: HashMap<Coord, T>,
     default: T,
+    /// Corners of the written cells, kept up to date on every write and removal
+    bounds: Option<(Coord, Coord)>,
 }
 
 impl<T: Clone + Debug> HashBoard<T> {
@@ -15,32 +17,17 @@ impl<T: Clone + Debug> HashBoard<T> {
         Self {
             coord_mapping: HashMap::new(),
             default,
+            bounds: None,
         }
     }
 
     pub(crate) fn as_grid(&self) -> Option<(Coord, Grid<T>)> {
-        let (min_x, max_x) = self
-            .coord_mapping
-            .keys()
-            .copied()
-            .map(|coord| coord.x)
-            .minmax()
-            .into_option()?;
-
-        let (min_y, max_y) = self
-            .coord_mapping
-            .keys()
-            .copied()
-            .map(|coord| coord.y)
-            .minmax()
-            .into_option()?;
-
-        let width = max_x.abs_diff(min_x) as usize + 1;
-        let height = max_y.abs_diff(min_y) as usize + 1;
+        let (delta, bottom_right) = self.bounds()?;
 
-        let mut grid = Grid::with_value(width, height, self.default.clone());
+        let width = bottom_right.x().abs_diff(delta.x()) as usize + 1;
+        let height = bottom_right.y().abs_diff(delta.y()) as usize + 1;
 
-	    let delta = Coord::new(min_x, min_y);
+        let mut grid = Grid::with_value(width, height, self.default.clone());
 
         self.coord_mapping.iter().for_each(|(&coord, value)| {
             grid.write(coord - delta, value.clone())
@@ -49,6 +36,70 @@ impl<T: Clone + Debug> HashBoard<T> {
 
         Some((delta, grid))
     }
+
+    /// Forgets a written cell, which reads as the default again
+    #[allow(dead_code)]
+    pub(crate) fn remove(&mut self, coord: Coord) -> Option<T> {
+        let removed = self.coord_mapping.remove(&coord)?;
+
+        // Only removing a cell on the edge can shrink the bounds
+        if let Some((top_left, bottom_right)) = self.bounds {
+            let on_edge = coord.x() == top_left.x()
+                || coord.x() == bottom_right.x()
+                || coord.y() == top_left.y()
+                || coord.y() == bottom_right.y();
+
+            if on_edge {
+                self.bounds = Self::compute_bounds(self.coord_mapping.keys().copied());
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// Draws the bounds row by row, using `unexplored` for cells that were never written
+    pub(crate) fn render(&self, glyph: impl Fn(&T) -> char, unexplored: char) -> String {
+        let Some((top_left, bottom_right)) = self.bounds else {
+            return String::new();
+        };
+
+        let mut rendered = String::new();
+        for y in top_left.y()..=bottom_right.y() {
+            for x in top_left.x()..=bottom_right.x() {
+                let cell = self.coord_mapping.get(&Coord::new(x, y));
+                rendered.push(cell.map_or(unexplored, &glyph));
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    #[allow(dead_code)]
+    fn compute_bounds(coords: impl Iterator<Item = Coord>) -> Option<(Coord, Coord)> {
+        coords.fold(None, |bounds, coord| Some(Self::extend(bounds, coord)))
+    }
+
+    fn extend(bounds: Option<(Coord, Coord)>, coord: Coord) -> (Coord, Coord) {
+        match bounds {
+            None => (coord, coord),
+            Some((top_left, bottom_right)) => (
+                Coord::new(top_left.x().min(coord.x()), top_left.y().min(coord.y())),
+                Coord::new(bottom_right.x().max(coord.x()), bottom_right.y().max(coord.y())),
+            ),
+        }
+    }
+}
+
+impl<T: Clone + Debug + PartialEq> HashBoard<T> {
+    /// Written cells whose value differs from the default
+    #[allow(dead_code)]
+    pub(crate) fn non_default(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coord_mapping
+            .iter()
+            .filter(|&(_coord, value)| *value != self.default)
+            .map(|(&coord, value)| (coord, value))
+    }
 }
 
 impl<T: Clone + Default + Debug> Default for HashBoard<T> {
@@ -64,14 +115,64 @@ impl<T: Clone + Debug> Board<T> for HashBoard<T> {
 
     fn write(&mut self, coord: Coord, value: T) -> Result<(), anyhow::Error> {
         self.coord_mapping.insert(coord, value);
+        self.bounds = Some(Self::extend(self.bounds, coord));
 
         Ok(())
     }
 
-    fn elements(&self) -> Vec<(Coord, T)> {
-        self.coord_mapping
-            .iter()
-            .map(|(&coord, value)| (coord, value.clone()))
-            .collect_vec()
+    fn iter(&self) -> Box<dyn Iterator<Item = (Coord, &T)> + '_> {
+        Box::new(
+            self.coord_mapping
+                .iter()
+                .map(|(&coord, value)| (coord, value)),
+        )
+    }
+
+    fn bounds(&self) -> Option<(Coord, Coord)> {
+        self.bounds
+    }
+}
+
+impl<T: Clone + Debug + Display> Display for HashBoard<T> {
+    /// First character of each value, with blanks for cells never written
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rendered = self.render(|value| value.to_string().chars().next().unwrap_or(' '), ' ');
+        write!(f, "{rendered}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_bounds() {
+        let mut board = HashBoard::new(0);
+        board.write(Coord::new(-2, 1), 1).unwrap();
+        board.write(Coord::new(3, -1), 0).unwrap();
+        board.write(Coord::new(0, 4), 2).unwrap();
+        assert_eq!(board.bounds(), Some((Coord::new(-2, -1), Coord::new(3, 4))));
+
+        assert_eq!(board.remove(Coord::new(3, -1)), Some(0));
+        assert_eq!(board.remove(Coord::new(3, -1)), None);
+        assert_eq!(board.bounds(), Some((Coord::new(-2, 1), Coord::new(0, 4))));
+
+        board.write(Coord::new(-1, 2), 0).unwrap();
+        let mut non_default = board
+            .non_default()
+            .map(|(coord, _value)| coord)
+            .collect::<Vec<_>>();
+        non_default.sort_by_key(|coord| (coord.y(), coord.x()));
+        assert_eq!(non_default, [Coord::new(-2, 1), Coord::new(0, 4)]);
+
+        assert_eq!(
+            board.render(|value| char::from(b'0' + *value as u8), '?'),
+            "1??\n?0?\n???\n??2\n"
+        );
+
+        board.remove(Coord::new(-2, 1));
+        board.remove(Coord::new(-1, 2));
+        board.remove(Coord::new(0, 4));
+        assert_eq!(board.bounds(), None);
     }
 }
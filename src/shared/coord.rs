@@ -1,83 +1,56 @@
+use crate::shared::point::Point;
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
 use strum_macros::EnumString;
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
-pub struct Coord {
-    pub(crate) x: i32,
-    pub(crate) y: i32,
-}
+/// Point on a plane, with `y` growing downwards
+pub type Coord = Point<2>;
 
-impl Coord {
-    pub(crate) fn new(x: i32, y: i32) -> Coord {
-        Coord { x, y }
+impl Point<2> {
+    pub(crate) const fn new(x: i32, y: i32) -> Coord {
+        Self::from_axes([x, y])
     }
 
-    pub(crate) fn manhattan(&self) -> u32 {
-        self.x.unsigned_abs() + self.y.unsigned_abs()
+    pub(crate) fn x(&self) -> i32 {
+        self[0]
     }
 
-    pub(crate) fn module(&self) -> f64 {
-        let x = self.x as f64;
-        let y = self.y as f64;
-
-        (x * x + y * y).sqrt()
+    pub(crate) fn y(&self) -> i32 {
+        self[1]
     }
 
-    pub(crate) fn cross(&self) -> impl Iterator<Item = Self> {
+    pub(crate) fn cross(self) -> impl Iterator<Item = Self> {
         CLOCKWISE
             .iter()
             .copied()
             .map(Self::from)
-            .map(|delta| *self + delta)
+            .map(move |delta| self + delta)
     }
-}
 
-impl Display for Coord {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-impl Add<Coord> for Coord {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
-impl Sub<Coord> for Coord {
-    type Output = Coord;
-
-    fn sub(self, rhs: Coord) -> Self::Output {
-        Self::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
-
-impl SubAssign for Coord {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
+    /// The eight cells around this one, diagonals included, clockwise from the one above
+    #[allow(dead_code)]
+    pub(crate) fn ring(self) -> impl Iterator<Item = Self> {
+        CLOCKWISE8
+            .iter()
+            .copied()
+            .map(Self::from)
+            .map(move |delta| self + delta)
     }
-}
-
-impl Neg for Coord {
-    type Output = Coord;
 
-    fn neg(self) -> Self::Output {
-        Self::new(-self.x, -self.y)
+    /// Rotated about the origin by quarter turns, clockwise on screen when positive
+    #[allow(dead_code)]
+    pub(crate) fn rotate(self, quarter_turns: i32) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Self::new(-self.y(), self.x()),
+            2 => -self,
+            _ => Self::new(self.y(), -self.x()),
+        }
     }
 }
 
-impl Default for Coord {
-    fn default() -> Self {
-        Self::new(0, 0)
+impl Display for Coord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x(), self.y())
     }
 }
 
@@ -101,26 +74,32 @@ const CLOCKWISE: [Direction; 4] = [
 ];
 
 impl Direction {
-    pub(crate) fn turn_right(&self) -> Self {
-        let idx = CLOCKWISE
-            .iter()
-            .position(|d| d == self)
-            .expect("Find direction position");
-
-        let idx = (idx + 1) % CLOCKWISE.len();
+    /// Turned clockwise by the given number of quarter turns, counter-clockwise when negative
+    pub(crate) fn turn(&self, quarter_turns: i32) -> Self {
+        CLOCKWISE[(*self as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
 
-        CLOCKWISE[idx]
+    pub(crate) fn turn_right(&self) -> Self {
+        self.turn(1)
     }
 
     pub(crate) fn turn_left(&self) -> Self {
-        let idx = CLOCKWISE
-            .iter()
-            .position(|d| d == self)
-            .expect("Find direction position");
+        self.turn(-1)
+    }
 
-        let idx = (CLOCKWISE.len() + idx - 1) % CLOCKWISE.len();
+    #[allow(dead_code)]
+    pub(crate) fn reverse(&self) -> Self {
+        self.turn(2)
+    }
 
-        CLOCKWISE[idx]
+    /// Character pointing this way, for drawing something that faces a direction
+    pub(crate) fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
     }
 }
 
@@ -139,12 +118,144 @@ impl TryFrom<Coord> for Direction {
     type Error = Coord;
 
     fn try_from(value: Coord) -> Result<Self, Self::Error> {
+        match value.axes() {
+            [0, -1] => Ok(Direction::Up),
+            [1, 0] => Ok(Direction::Right),
+            [0, 1] => Ok(Direction::Down),
+            [-1, 0] => Ok(Direction::Left),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Compass direction including the diagonals, in clockwise order
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, EnumString, strum_macros::Display)]
+pub enum Direction8 {
+    #[strum(serialize = "N")]
+    Up,
+    #[strum(serialize = "NE")]
+    UpRight,
+    #[strum(serialize = "E")]
+    Right,
+    #[strum(serialize = "SE")]
+    DownRight,
+    #[strum(serialize = "S")]
+    Down,
+    #[strum(serialize = "SW")]
+    DownLeft,
+    #[strum(serialize = "W")]
+    Left,
+    #[strum(serialize = "NW")]
+    UpLeft,
+}
+
+const CLOCKWISE8: [Direction8; 8] = [
+    Direction8::Up,
+    Direction8::UpRight,
+    Direction8::Right,
+    Direction8::DownRight,
+    Direction8::Down,
+    Direction8::DownLeft,
+    Direction8::Left,
+    Direction8::UpLeft,
+];
+
+impl Direction8 {
+    /// Turned clockwise by the given number of 45 degree steps, counter-clockwise when negative
+    #[allow(dead_code)]
+    pub(crate) fn turn(&self, eighth_turns: i32) -> Self {
+        CLOCKWISE8[(*self as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn reverse(&self) -> Self {
+        self.turn(4)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn is_diagonal(&self) -> bool {
+        Direction::try_from(*self).is_err()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        CLOCKWISE8[value as usize * 2]
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = Direction8;
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        let index = value as usize;
+
+        if index.is_multiple_of(2) {
+            Ok(CLOCKWISE[index / 2])
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl From<Direction8> for Coord {
+    fn from(value: Direction8) -> Self {
         match value {
-            Coord { x: 0, y: -1 } => Ok(Direction::Up),
-            Coord { x: 1, y: 0 } => Ok(Direction::Right),
-            Coord { x: 0, y: 1 } => Ok(Direction::Down),
-            Coord { x: -1, y: 0 } => Ok(Direction::Left),
-            value => Err(value),
+            Direction8::Up => Coord::new(0, -1),
+            Direction8::UpRight => Coord::new(1, -1),
+            Direction8::Right => Coord::new(1, 0),
+            Direction8::DownRight => Coord::new(1, 1),
+            Direction8::Down => Coord::new(0, 1),
+            Direction8::DownLeft => Coord::new(-1, 1),
+            Direction8::Left => Coord::new(-1, 0),
+            Direction8::UpLeft => Coord::new(-1, -1),
+        }
+    }
+}
+
+impl TryFrom<Coord> for Direction8 {
+    type Error = Coord;
+
+    fn try_from(value: Coord) -> Result<Self, Self::Error> {
+        CLOCKWISE8
+            .iter()
+            .copied()
+            .find(|&direction| Coord::from(direction) == value)
+            .ok_or(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn(-5), Direction::Left);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+
+        assert_eq!(Direction8::UpLeft.turn(1), Direction8::Up);
+        assert_eq!(Direction8::Up.turn(-3), Direction8::DownLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert!(Direction8::DownRight.is_diagonal());
+        assert_eq!(Direction8::from(Direction::Down), Direction8::Down);
+        assert_eq!(Direction::try_from(Direction8::Left), Ok(Direction::Left));
+
+        let coord = Coord::new(3, -1);
+        assert_eq!(coord.rotate(1), Coord::new(1, 3));
+        assert_eq!(coord.rotate(-1), coord.rotate(3));
+        assert_eq!(coord.rotate(2), -coord);
+        for direction in CLOCKWISE {
+            assert_eq!(
+                Coord::from(direction).rotate(1),
+                Coord::from(direction.turn_right())
+            );
         }
+
+        let ring = Coord::new(5, 5).ring().collect::<Vec<_>>();
+        assert_eq!(ring.len(), 8);
+        assert_eq!(ring[0], Coord::new(5, 4));
+        assert_eq!(ring[7], Coord::new(4, 4));
     }
 }
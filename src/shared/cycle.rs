@@ -0,0 +1,210 @@
+use num::Integer;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+/// Shape of a sequence of states that eventually repeats: after `tail` steps, states come back
+/// every `period` steps
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Cycle {
+    pub(crate) tail: u64,
+    pub(crate) period: u64,
+}
+
+impl Cycle {
+    /// Earliest step with the same state as `step`
+    #[allow(dead_code)]
+    pub(crate) fn equivalent_step(&self, step: u64) -> u64 {
+        if step < self.tail {
+            step
+        } else {
+            self.tail + (step - self.tail) % self.period
+        }
+    }
+
+    /// State reached after `target` steps, only stepping through the tail and at most one period
+    #[allow(dead_code)]
+    pub(crate) fn state_at<T>(&self, mut state: T, mut step: impl FnMut(&mut T), target: u64) -> T {
+        for _step in 0..self.equivalent_step(target) {
+            step(&mut state);
+        }
+
+        state
+    }
+
+    /// Cycle of two independent sequences stepped together, which repeats once both have gone
+    /// past their tail and around a whole number of their periods
+    pub(crate) fn combine(&self, other: &Self) -> Self {
+        Self {
+            tail: self.tail.max(other.tail),
+            period: self.period.lcm(&other.period),
+        }
+    }
+}
+
+/// State after stepping a copy of `state` the given number of times
+fn stepped<T: Clone>(state: &T, step: &mut impl FnMut(&mut T), steps: u64) -> T {
+    let mut state = state.clone();
+    for _step in 0..steps {
+        step(&mut state);
+    }
+
+    state
+}
+
+/// Tortoise and hare: the hare steps twice as fast until they meet inside the cycle
+#[allow(dead_code)]
+pub(crate) fn floyd<T: Clone + Eq>(initial: T, mut step: impl FnMut(&mut T)) -> Cycle {
+    let mut tortoise = stepped(&initial, &mut step, 1);
+    let mut hare = stepped(&tortoise, &mut step, 1);
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    // The meeting point is as many steps from the cycle's start as the initial state is
+    let mut tortoise = initial;
+    let mut tail = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        tail += 1;
+    }
+
+    let mut hare = stepped(&tortoise, &mut step, 1);
+    let mut period = 1;
+    while tortoise != hare {
+        step(&mut hare);
+        period += 1;
+    }
+
+    Cycle { tail, period }
+}
+
+/// Brent's algorithm, comparing states by `key` so that only part of a state needs to repeat.
+///
+/// States are stepped in place. The tortoise clones the hare each time it moves, at powers of
+/// two, and finding the tail steps another `period` states plus twice the tail from the start.
+pub(crate) fn brent_by_key<T: Clone, K: Eq + ?Sized>(
+    initial: T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> &K,
+) -> Cycle {
+    // The tortoise waits at powers of two for the hare to come back around
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = stepped(&initial, &mut step, 1);
+    while key(&tortoise) != key(&hare) {
+        if power == period {
+            tortoise.clone_from(&hare);
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        period += 1;
+    }
+
+    let mut hare = stepped(&initial, &mut step, period);
+    let mut tortoise = initial;
+    let mut tail = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        tail += 1;
+    }
+
+    Cycle { tail, period }
+}
+
+/// Brent's algorithm, which needs fewer steps than `floyd` and keeps only two states around
+#[allow(dead_code)]
+pub(crate) fn brent<T: Clone + Eq>(initial: T, step: impl FnMut(&mut T)) -> Cycle {
+    fn whole<T>(state: &T) -> &T {
+        state
+    }
+
+    brent_by_key(initial, step, whole)
+}
+
+/// Remembers the step at which every fingerprint was seen, stepping each state only once. The
+/// fingerprint must tell apart any two states that differ.
+#[allow(dead_code)]
+pub(crate) fn by_fingerprint<T, K: Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&mut T),
+    fingerprint: impl Fn(&T) -> K,
+) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        match seen.entry(fingerprint(&state)) {
+            Entry::Occupied(first) => {
+                return Cycle {
+                    tail: *first.get(),
+                    period: index - first.get(),
+                };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+        step(&mut state);
+    }
+
+    unreachable!("Some state repeats before running out of steps")
+}
+
+/// State after `target` steps, however large, of a sequence that eventually repeats
+#[allow(dead_code)]
+pub(crate) fn extrapolate<T: Clone + Eq>(
+    initial: T,
+    mut step: impl FnMut(&mut T),
+    target: u64,
+) -> T {
+    let cycle = brent(initial.clone(), &mut step);
+    cycle.state_at(initial, step, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_cycles() {
+        // 0, 1, 2, 3, then 4 to 9 over and over
+        let step = |n: &mut u32| *n = if *n == 9 { 4 } else { *n + 1 };
+        let expected = Cycle { tail: 4, period: 6 };
+
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(by_fingerprint(0, step, |&n| n), expected);
+        assert_eq!(brent(4, step), Cycle { tail: 0, period: 6 });
+
+        // Counting the steps as well, only the first number repeats
+        let counted = |(n, count): &mut (u32, u64)| {
+            step(n);
+            *count += 1;
+        };
+        fn number((n, _count): &(u32, u64)) -> &u32 {
+            n
+        }
+        assert_eq!(brent_by_key((0, 0), counted, number), expected);
+
+        assert_eq!(extrapolate(0, step, 3), 3);
+        assert_eq!(extrapolate(0, step, 10), 4);
+        assert_eq!(extrapolate(0, step, 1_000_000_000_000_000), 4);
+        assert_eq!(expected.state_at(0, step, 1_000_000_000_000_001), 5);
+
+        let combined = expected.combine(&Cycle { tail: 1, period: 4 });
+        assert_eq!(
+            combined,
+            Cycle {
+                tail: 4,
+                period: 12
+            }
+        );
+    }
+}
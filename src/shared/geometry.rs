@@ -0,0 +1,220 @@
+use crate::shared::coord::Coord;
+use gcd::Gcd;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+/// Splits a non-zero vector into the number of lattice steps it spans and the smallest step in
+/// the same direction
+pub(crate) fn reduce(delta: Coord) -> Option<(u32, Coord)> {
+    let steps = delta.x().unsigned_abs().gcd(delta.y().unsigned_abs());
+
+    if steps == 0 {
+        return None;
+    }
+
+    Some((steps, delta.map(|value| value / steps as i32)))
+}
+
+/// Exact direction of a non-zero vector, ordered clockwise on screen starting straight up
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub(crate) struct Angle {
+    /// Smallest lattice step in this direction, so that equal angles have equal steps
+    step: Coord,
+}
+
+impl Angle {
+    #[allow(dead_code)]
+    pub(crate) fn of(delta: Coord) -> Option<Self> {
+        reduce(delta).map(|(_steps, step)| Self { step })
+    }
+
+    /// Whether the angle lies in the first half turn, from straight up included to straight
+    /// down excluded
+    fn is_right_half(&self) -> bool {
+        self.step.x() > 0 || (self.step.x() == 0 && self.step.y() < 0)
+    }
+}
+
+impl Ord for Angle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let halves = other.is_right_half().cmp(&self.is_right_half());
+
+        // Within a half turn, the cross product tells which vector is reached first when turning
+        // clockwise, which is the positive direction with `y` pointing down
+        halves.then_with(|| {
+            let cross = i64::from(self.step.x()) * i64::from(other.step.y())
+                - i64::from(self.step.y()) * i64::from(other.step.x());
+            0.cmp(&cross)
+        })
+    }
+}
+
+impl PartialOrd for Angle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Every lattice point on the segment, both ends included
+#[allow(dead_code)]
+pub(crate) fn lattice_points(from: Coord, to: Coord) -> impl Iterator<Item = Coord> {
+    let (steps, step) = reduce(to - from).unwrap_or((0, Coord::default()));
+
+    (0..=steps as i32).map(move |index| from + step * index)
+}
+
+/// Cells a drawn line from one point to the other goes through, both ends included, following
+/// Bresenham's algorithm
+#[allow(dead_code)]
+pub(crate) fn bresenham(from: Coord, to: Coord) -> impl Iterator<Item = Coord> {
+    let delta = to - from;
+    let (dx, dy) = (delta.x().abs(), -delta.y().abs());
+    let step = delta.signum();
+
+    let mut current = Some(from);
+    let mut error = dx + dy;
+
+    std::iter::from_fn(move || {
+        let coord = current?;
+
+        current = (coord != to).then(|| {
+            let mut next = coord;
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                next[0] += step.x();
+            }
+            if doubled <= dx {
+                error += dx;
+                next[1] += step.y();
+            }
+            next
+        });
+
+        Some(coord)
+    })
+}
+
+/// Whether nothing in `blockers` lies exactly on the segment between the two points
+#[allow(dead_code)]
+pub(crate) fn in_sight(from: Coord, to: Coord, blockers: &HashSet<Coord>) -> bool {
+    lattice_points(from, to)
+        .filter(|&coord| coord != from && coord != to)
+        .all(|coord| !blockers.contains(&coord))
+}
+
+/// Points around `center` grouped by angle, each group ordered from nearest to furthest
+fn by_angle(center: Coord, points: &[Coord]) -> BTreeMap<Angle, Vec<(u32, Coord)>> {
+    let mut groups = BTreeMap::<Angle, Vec<(u32, Coord)>>::new();
+
+    for &point in points {
+        if let Some((steps, step)) = reduce(point - center) {
+            groups
+                .entry(Angle { step })
+                .or_default()
+                .push((steps, point));
+        }
+    }
+
+    for group in groups.values_mut() {
+        group.sort_unstable();
+    }
+
+    groups
+}
+
+/// Points seen from `center`, i.e. the nearest one along every angle
+pub(crate) fn visible(center: Coord, points: &[Coord]) -> Vec<Coord> {
+    by_angle(center, points)
+        .into_values()
+        .map(|group| group[0].1)
+        .collect()
+}
+
+/// Order in which a beam turning clockwise from straight up hits the points, taking only the
+/// nearest remaining point along an angle on each turn
+pub(crate) fn sweep(center: Coord, points: &[Coord]) -> Vec<Coord> {
+    let groups = by_angle(center, points);
+    let turns = groups.values().map(Vec::len).max().unwrap_or(0);
+
+    (0..turns)
+        .flat_map(|turn| groups.values().filter_map(move |group| group.get(turn)))
+        .map(|&(_steps, point)| point)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn angle_order() {
+        let clockwise = [
+            Coord::new(0, -1),
+            Coord::new(1, -100),
+            Coord::new(1, -1),
+            Coord::new(1, 0),
+            Coord::new(3, 2),
+            Coord::new(0, 1),
+            Coord::new(-1, 1),
+            Coord::new(-1, 0),
+            Coord::new(-100, -1),
+            Coord::new(-1, -100),
+        ];
+
+        let angles = clockwise.map(|delta| Angle::of(delta).unwrap());
+        assert!(angles.iter().tuple_windows().all(|(a, b)| a < b));
+        assert_eq!(Angle::of(Coord::new(6, 4)), Angle::of(Coord::new(3, 2)));
+        assert_eq!(Angle::of(Coord::default()), None);
+    }
+
+    #[test]
+    fn lines_and_sight() {
+        let exact = lattice_points(Coord::new(1, 1), Coord::new(7, -3)).collect_vec();
+        assert_eq!(
+            exact,
+            [Coord::new(1, 1), Coord::new(4, -1), Coord::new(7, -3)]
+        );
+
+        let drawn = bresenham(Coord::new(0, 0), Coord::new(5, -2)).collect_vec();
+        assert_eq!(drawn.len(), 6);
+        assert_eq!(drawn.first(), Some(&Coord::new(0, 0)));
+        assert_eq!(drawn.last(), Some(&Coord::new(5, -2)));
+        assert!(
+            drawn
+                .iter()
+                .tuple_windows()
+                .all(|(&a, &b)| (b - a).chebyshev() == 1)
+        );
+        assert_eq!(
+            bresenham(Coord::new(2, 2), Coord::new(2, 2)).collect_vec(),
+            [Coord::new(2, 2)]
+        );
+
+        let blockers = HashSet::from([Coord::new(4, -1), Coord::new(2, 0)]);
+        assert!(!in_sight(Coord::new(1, 1), Coord::new(7, -3), &blockers));
+        assert!(in_sight(Coord::new(1, 1), Coord::new(4, -1), &blockers));
+        assert!(!in_sight(Coord::new(1, 1), Coord::new(3, -1), &blockers));
+        assert!(in_sight(Coord::new(1, 1), Coord::new(3, 0), &blockers));
+
+        let points = [
+            Coord::new(0, -2),
+            Coord::new(0, -1),
+            Coord::new(2, 0),
+            Coord::new(-1, 0),
+            Coord::new(0, 0),
+        ];
+        assert_eq!(visible(Coord::new(0, 0), &points).len(), 3);
+        assert_eq!(
+            sweep(Coord::new(0, 0), &points),
+            [
+                Coord::new(0, -1),
+                Coord::new(2, 0),
+                Coord::new(-1, 0),
+                Coord::new(0, -2)
+            ]
+        );
+    }
+}
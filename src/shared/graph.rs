@@ -0,0 +1,336 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// Node of a `Graph`, standing for the name it was interned from
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
+pub(crate) struct NodeId(usize);
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum GraphError {
+    #[error("unknown node {0}")]
+    UnknownNode(String),
+    #[error("cycle through {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Closest node that both nodes descend from, with how many edges up each of them it is
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct CommonAncestor {
+    pub(crate) node: NodeId,
+    pub(crate) first_distance: u32,
+    pub(crate) second_distance: u32,
+}
+
+/// Directed graph over named nodes, with a weight on every edge
+#[derive(Clone, Debug)]
+pub(crate) struct Graph<E = ()> {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    outgoing: Vec<Vec<(NodeId, E)>>,
+    incoming: Vec<Vec<NodeId>>,
+}
+
+impl<E> Graph<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    pub(crate) fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str, E)>) -> Self {
+        let mut graph = Self::new();
+        for (from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+
+        graph
+    }
+
+    /// Node with this name, added if it is new
+    pub(crate) fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = NodeId(self.names.len());
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+
+        id
+    }
+
+    pub(crate) fn add_edge(&mut self, from: &str, to: &str, weight: E) -> (NodeId, NodeId) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.outgoing[from.0].push((to, weight));
+        self.incoming[to.0].push(from);
+
+        (from, to)
+    }
+
+    pub(crate) fn id(&self, name: &str) -> Result<NodeId, GraphError> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| GraphError::UnknownNode(name.to_owned()))
+    }
+
+    pub(crate) fn name(&self, id: NodeId) -> &str {
+        &self.names[id.0]
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = NodeId> + use<E> {
+        (0..self.names.len()).map(NodeId)
+    }
+
+    /// Nodes this one has an edge to, with the edge weights
+    pub(crate) fn edges(&self, id: NodeId) -> &[(NodeId, E)] {
+        &self.outgoing[id.0]
+    }
+
+    /// Nodes with an edge to this one
+    pub(crate) fn parents(&self, id: NodeId) -> &[NodeId] {
+        &self.incoming[id.0]
+    }
+
+    /// Nodes without any edge coming in
+    pub(crate) fn roots(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes().filter(|&id| self.parents(id).is_empty())
+    }
+
+    /// Every node after all the nodes with an edge to it
+    pub(crate) fn topological_sort(&self) -> Result<Vec<NodeId>, GraphError> {
+        let mut waiting_for = self.incoming.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut ready = self.roots().collect::<VecDeque<_>>();
+        let mut sorted = Vec::with_capacity(self.len());
+
+        while let Some(id) = ready.pop_front() {
+            sorted.push(id);
+
+            for &(next, _) in self.edges(id) {
+                waiting_for[next.0] -= 1;
+                if waiting_for[next.0] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if sorted.len() < self.len() {
+            let cycle = self.find_cycle().unwrap_or_default();
+            return Err(GraphError::Cycle(
+                cycle
+                    .into_iter()
+                    .map(|id| self.name(id).to_owned())
+                    .collect(),
+            ));
+        }
+
+        Ok(sorted)
+    }
+
+    /// Nodes along some cycle, starting and ending with the same node
+    pub(crate) fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Copy, Clone, Eq, PartialEq)]
+        enum Visit {
+            New,
+            Open,
+            Done,
+        }
+
+        let mut visits = vec![Visit::New; self.len()];
+
+        for start in self.nodes() {
+            if visits[start.0] != Visit::New {
+                continue;
+            }
+
+            // Depth first, keeping the open nodes and how many of their edges were followed
+            let mut stack = vec![(start, 0)];
+            visits[start.0] = Visit::Open;
+
+            while let Some((id, followed)) = stack.last_mut() {
+                let Some(&(next, _)) = self.edges(*id).get(*followed) else {
+                    visits[id.0] = Visit::Done;
+                    stack.pop();
+                    continue;
+                };
+                *followed += 1;
+
+                match visits[next.0] {
+                    Visit::New => {
+                        visits[next.0] = Visit::Open;
+                        stack.push((next, 0));
+                    }
+                    Visit::Open => {
+                        let from = stack.iter().position(|&(open, _)| open == next)?;
+                        let mut cycle = stack[from..]
+                            .iter()
+                            .map(|&(open, _)| open)
+                            .collect::<Vec<_>>();
+                        cycle.push(next);
+                        return Some(cycle);
+                    }
+                    Visit::Done => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Fewest edges from a root down to each node, or `None` for nodes only reachable from cycles
+    pub(crate) fn depths(&self) -> Vec<Option<u32>> {
+        let mut depths = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+
+        for root in self.roots() {
+            depths[root.0] = Some(0);
+            queue.push_back((root, 0));
+        }
+
+        while let Some((id, depth)) = queue.pop_front() {
+            for &(next, _) in self.edges(id) {
+                if depths[next.0].is_none() {
+                    depths[next.0] = Some(depth + 1);
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        depths
+    }
+
+    /// Fewest edges up from a node to each of its ancestors, itself included
+    fn ancestor_distances(&self, id: NodeId) -> HashMap<NodeId, u32> {
+        let mut distances = HashMap::from([(id, 0)]);
+        let mut queue = VecDeque::from([(id, 0)]);
+
+        while let Some((id, distance)) = queue.pop_front() {
+            for &parent in self.parents(id) {
+                if let Entry::Vacant(entry) = distances.entry(parent) {
+                    entry.insert(distance + 1);
+                    queue.push_back((parent, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Common ancestor closest to both nodes, counting the edges up from either of them
+    pub(crate) fn lowest_common_ancestor(
+        &self,
+        first: NodeId,
+        second: NodeId,
+    ) -> Option<CommonAncestor> {
+        let first_distances = self.ancestor_distances(first);
+
+        self.ancestor_distances(second)
+            .into_iter()
+            .filter_map(|(node, second_distance)| {
+                first_distances
+                    .get(&node)
+                    .map(|&first_distance| CommonAncestor {
+                        node,
+                        first_distance,
+                        second_distance,
+                    })
+            })
+            .min_by_key(|ancestor| {
+                (
+                    ancestor.first_distance + ancestor.second_distance,
+                    ancestor.node,
+                )
+            })
+    }
+
+    /// Graphviz description of the graph, labelling edges with `label`
+    pub(crate) fn to_dot(&self, label: impl Fn(&E) -> Option<String>) -> String {
+        let quoted = |id: NodeId| format!("{:?}", self.name(id));
+        let mut dot = String::from("digraph {\n");
+
+        for id in self.nodes() {
+            if self.edges(id).is_empty() && self.parents(id).is_empty() {
+                let _ = writeln!(dot, "    {};", quoted(id));
+            }
+
+            for (to, weight) in self.edges(id) {
+                let _ = match label(weight) {
+                    Some(label) => {
+                        writeln!(
+                            dot,
+                            "    {} -> {} [label={label:?}];",
+                            quoted(id),
+                            quoted(*to)
+                        )
+                    }
+                    None => writeln!(dot, "    {} -> {};", quoted(id), quoted(*to)),
+                };
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_queries() {
+        let mut graph = Graph::from_edges([
+            ("shirt", "tie", ()),
+            ("tie", "jacket", ()),
+            ("trousers", "shoes", ()),
+            ("trousers", "belt", ()),
+            ("belt", "jacket", ()),
+            ("socks", "shoes", ()),
+        ]);
+        graph.node("watch");
+        assert_eq!(graph.len(), 8);
+
+        let order = graph.topological_sort().unwrap();
+        let position = |name| order.iter().position(|&id| graph.name(id) == name).unwrap();
+        assert!(position("shirt") < position("tie") && position("tie") < position("jacket"));
+        assert!(position("belt") < position("jacket") && position("socks") < position("shoes"));
+
+        let depths = graph.depths();
+        assert_eq!(depths[graph.id("jacket").unwrap().0], Some(2));
+        assert_eq!(depths[graph.id("watch").unwrap().0], Some(0));
+
+        let (shoes, jacket) = (graph.id("shoes").unwrap(), graph.id("jacket").unwrap());
+        let ancestor = graph.lowest_common_ancestor(shoes, jacket).unwrap();
+        assert_eq!(graph.name(ancestor.node), "trousers");
+        assert_eq!((ancestor.first_distance, ancestor.second_distance), (1, 2));
+        assert!(
+            graph
+                .lowest_common_ancestor(shoes, graph.id("tie").unwrap())
+                .is_none()
+        );
+        assert!(graph.id("hat").is_err());
+
+        let dot = graph.to_dot(|_| None);
+        assert!(dot.starts_with("digraph {\n    \"shirt\" -> \"tie\";\n"));
+        assert!(dot.contains("    \"watch\";\n"));
+
+        assert_eq!(graph.find_cycle(), None);
+        graph.add_edge("jacket", "shirt", ());
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+        assert!(
+            matches!(graph.topological_sort(), Err(GraphError::Cycle(names)) if names.len() == 4)
+        );
+    }
+}
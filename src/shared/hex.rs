@@ -0,0 +1,178 @@
+use crate::shared::point::Point;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use strum_macros::EnumString;
+
+/// Cell of a grid of flat-topped hexagons, kept as cube coordinates whose axes sum to zero
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd, Default)]
+pub(crate) struct Hex {
+    cube: Point<3>,
+}
+
+impl Hex {
+    /// Axial coordinates: `q` grows towards the lower right, `r` grows downwards
+    pub(crate) fn new(q: i32, r: i32) -> Self {
+        Self {
+            cube: Point::from_axes([q, r, -q - r]),
+        }
+    }
+
+    pub(crate) fn q(&self) -> i32 {
+        self.cube[0]
+    }
+
+    pub(crate) fn r(&self) -> i32 {
+        self.cube[1]
+    }
+
+    /// Number of steps to the origin
+    pub(crate) fn distance(&self) -> u32 {
+        self.cube.manhattan() / 2
+    }
+
+    /// The six surrounding cells, clockwise from the one above
+    pub(crate) fn neighbours(self) -> impl Iterator<Item = Self> {
+        HEX_CLOCKWISE
+            .iter()
+            .copied()
+            .map(Self::from)
+            .map(move |delta| self + delta)
+    }
+
+    /// Rotated about the origin by sixths of a turn, clockwise when positive
+    pub(crate) fn rotate(self, sixth_turns: i32) -> Self {
+        (0..sixth_turns.rem_euclid(6)).fold(self, |hex, _turn| {
+            let [q, r, s] = hex.cube.axes();
+            Self {
+                cube: Point::from_axes([-r, -s, -q]),
+            }
+        })
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[q={}, r={}]", self.q(), self.r())
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            cube: self.cube + rhs.cube,
+        }
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            cube: self.cube - rhs.cube,
+        }
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self { cube: -self.cube }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum HexDirection {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+const HEX_CLOCKWISE: [HexDirection; 6] = [
+    HexDirection::N,
+    HexDirection::NE,
+    HexDirection::SE,
+    HexDirection::S,
+    HexDirection::SW,
+    HexDirection::NW,
+];
+
+impl HexDirection {
+    /// Turned clockwise by the given number of 60 degree steps, counter-clockwise when negative
+    pub(crate) fn turn(&self, sixth_turns: i32) -> Self {
+        HEX_CLOCKWISE[(*self as i32 + sixth_turns).rem_euclid(6) as usize]
+    }
+
+    pub(crate) fn reverse(&self) -> Self {
+        self.turn(3)
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(value: HexDirection) -> Self {
+        match value {
+            HexDirection::N => Hex::new(0, -1),
+            HexDirection::NE => Hex::new(1, -1),
+            HexDirection::SE => Hex::new(1, 0),
+            HexDirection::S => Hex::new(0, 1),
+            HexDirection::SW => Hex::new(-1, 1),
+            HexDirection::NW => Hex::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn hex_walks() {
+        let walk = |steps: &str| {
+            steps
+                .split(',')
+                .map(|step| HexDirection::from_str(step).unwrap())
+                .map(Hex::from)
+                .fold(Hex::default(), Hex::add)
+        };
+
+        assert_eq!(walk("ne,ne,ne").distance(), 3);
+        assert_eq!(walk("ne,ne,sw,sw").distance(), 0);
+        assert_eq!(walk("ne,ne,s,s").distance(), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").distance(), 3);
+
+        let hex = Hex::new(2, -1);
+        assert_eq!(hex.rotate(6), hex);
+        assert_eq!(hex.rotate(-1), hex.rotate(5));
+        assert_eq!(hex.rotate(3), -hex);
+        assert_eq!(hex.rotate(1).distance(), hex.distance());
+        for direction in HEX_CLOCKWISE {
+            assert_eq!(Hex::from(direction).rotate(1), Hex::from(direction.turn(1)));
+            assert_eq!(Hex::from(direction.reverse()), -Hex::from(direction));
+        }
+
+        assert!(
+            hex.neighbours()
+                .all(|neighbour| (neighbour - hex).distance() == 1)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::shared::cycle::{self, Cycle};
+use crate::shared::point::Point;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::thread;
+
+/// Change in velocity along one axis for a body at `position`, pulled by another at `other`.
+/// Working one axis at a time keeps the axes independent of each other.
+pub(crate) type Pull = fn(position: i32, other: i32) -> i32;
+
+/// Pull of the puzzle's moons: one unit towards the other body, whatever the distance
+pub(crate) fn unit_pull(position: i32, other: i32) -> i32 {
+    (other - position).signum()
+}
+
+/// One unit away from the other body, pushing bodies apart
+pub(crate) fn repel_pull(position: i32, other: i32) -> i32 {
+    -unit_pull(position, other)
+}
+
+/// Pull picked by name: `unit` for the puzzle's, `repel` to push bodies apart
+pub(crate) fn pull_named(name: &str) -> anyhow::Result<Pull> {
+    match name {
+        "unit" => Ok(unit_pull),
+        "repel" => Ok(repel_pull),
+        _ => anyhow::bail!("Unknown pull {name:?}, expected unit or repel"),
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub(crate) struct Body<const N: usize> {
+    pub(crate) pos: Point<N>,
+    pub(crate) vel: Point<N>,
+}
+
+impl<const N: usize> Body<N> {
+    pub(crate) fn new(pos: Point<N>) -> Self {
+        Self {
+            pos,
+            vel: Point::default(),
+        }
+    }
+
+    pub(crate) fn potential_energy(&self) -> u32 {
+        self.pos.manhattan()
+    }
+
+    pub(crate) fn kinetic_energy(&self) -> u32 {
+        self.vel.manhattan()
+    }
+
+    pub(crate) fn total_energy(&self) -> u32 {
+        self.potential_energy() * self.kinetic_energy()
+    }
+}
+
+impl<const N: usize> Display for Body<N>
+where
+    Point<N>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pos={}, vel={}", self.pos, self.vel)
+    }
+}
+
+/// Any number of bodies pulling on each other in `N` dimensions
+#[derive(Clone, Debug)]
+pub(crate) struct Simulation<const N: usize> {
+    bodies: Vec<Body<N>>,
+    pull: Pull,
+    ticks: u64,
+}
+
+impl<const N: usize> Simulation<N> {
+    pub(crate) fn new(bodies: Vec<Body<N>>) -> Self {
+        Self {
+            bodies,
+            pull: unit_pull,
+            ticks: 0,
+        }
+    }
+
+    pub(crate) fn with_pull(mut self, pull: Pull) -> Self {
+        self.pull = pull;
+        self
+    }
+
+    pub(crate) fn bodies(&self) -> &[Body<N>] {
+        &self.bodies
+    }
+
+    /// Ticks simulated since the initial state
+    pub(crate) fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Every body is pulled by every other one, then moves by its new velocity
+    pub(crate) fn tick(&mut self) {
+        let pull = self.pull;
+        let positions = self.bodies.iter().map(|body| body.pos).collect_vec();
+
+        for (index, body) in self.bodies.iter_mut().enumerate() {
+            for (other_index, other) in positions.iter().enumerate() {
+                if index != other_index {
+                    body.vel += Point::from_axes(std::array::from_fn(|axis| {
+                        pull(body.pos[axis], other[axis])
+                    }));
+                }
+            }
+        }
+
+        for body in &mut self.bodies {
+            body.pos += body.vel;
+        }
+        self.ticks += 1;
+    }
+
+    pub(crate) fn run(&mut self, ticks: u64) {
+        for _tick in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Runs for `ticks` ticks, showing `observe` the initial state and the state after every tick
+    pub(crate) fn run_observed(
+        &mut self,
+        ticks: u64,
+        mut observe: impl FnMut(&Self) -> Result<(), anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        observe(self)?;
+        for _tick in 0..ticks {
+            self.tick();
+            observe(self)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn total_energy(&self) -> u32 {
+        self.bodies.iter().map(Body::total_energy).sum()
+    }
+
+    /// The simulation seen along a single axis
+    pub(crate) fn axis(&self, axis: usize) -> Simulation<1> {
+        Simulation {
+            bodies: self
+                .bodies
+                .iter()
+                .map(|body| Body {
+                    pos: Point::from_axes([body.pos[axis]]),
+                    vel: Point::from_axes([body.vel[axis]]),
+                })
+                .collect(),
+            pull: self.pull,
+            ticks: self.ticks,
+        }
+    }
+
+    /// Result of `f` for every axis, each simulated on a thread of its own
+    pub(crate) fn per_axis<R: Send>(&self, f: impl Fn(Simulation<1>) -> R + Sync) -> Vec<R> {
+        let f = &f;
+
+        thread::scope(|scope| {
+            let threads = (0..N)
+                .map(|axis| {
+                    let simulation = self.axis(axis);
+                    scope.spawn(move || f(simulation))
+                })
+                .collect_vec();
+
+            threads
+                .into_iter()
+                .map(|thread| thread.join().expect("Axis thread not to panic"))
+                .collect()
+        })
+    }
+
+    /// Same as `run`, running every axis on its own in parallel
+    pub(crate) fn run_by_axis(&mut self, ticks: u64) {
+        let axes = self.per_axis(|mut simulation| {
+            simulation.run(ticks);
+            simulation
+        });
+
+        for (axis, simulation) in axes.iter().enumerate() {
+            for (body, moved) in self.bodies.iter_mut().zip(simulation.bodies()) {
+                body.pos[axis] = moved.pos[0];
+                body.vel[axis] = moved.vel[0];
+            }
+        }
+        self.ticks += ticks;
+    }
+
+    /// How the simulation repeats, found one axis at a time since the whole simulation only
+    /// repeats once all of them do
+    pub(crate) fn cycle(&self) -> Cycle {
+        self.per_axis(|simulation| {
+            cycle::brent_by_key(simulation, Simulation::tick, Simulation::bodies)
+        })
+        .into_iter()
+        .reduce(|cycle, axis_cycle| cycle.combine(&axis_cycle))
+        .unwrap_or(Cycle { tail: 0, period: 1 })
+    }
+}
+
+impl<const N: usize> Display for Simulation<N>
+where
+    Point<N>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for body in &self.bodies {
+            writeln!(f, "{body}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::vect3::Vect3;
+
+    fn example() -> Simulation<3> {
+        Simulation::new(
+            [
+                Vect3::new(-1, 0, 2),
+                Vect3::new(2, -10, -7),
+                Vect3::new(4, -8, 8),
+                Vect3::new(3, 5, -1),
+            ]
+            .into_iter()
+            .map(Body::new)
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn moons() {
+        let mut simulation = example();
+        simulation.tick();
+        assert_eq!(
+            simulation.bodies()[0].to_string(),
+            "pos=<x=2, y=-1, z=1>, vel=<x=3, y=-1, z=-1>"
+        );
+
+        simulation.run(9);
+        assert_eq!((simulation.ticks(), simulation.total_energy()), (10, 179));
+
+        let mut by_axis = example();
+        by_axis.run_by_axis(10);
+        assert_eq!(by_axis.bodies(), simulation.bodies());
+
+        assert_eq!(
+            example().cycle(),
+            Cycle {
+                tail: 0,
+                period: 2772
+            }
+        );
+
+        // Five bodies, pushing each other away instead
+        let mut repelling = example().with_pull(repel_pull);
+        repelling.bodies.push(Body::new(Vect3::new(0, 0, 0)));
+        repelling.run(1);
+        assert_eq!(repelling.bodies()[4].vel, Vect3::new(-2, 1, 0));
+    }
+}
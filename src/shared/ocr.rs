@@ -0,0 +1,173 @@
+use crate::shared::board::{Board, Grid};
+use crate::shared::coord::Coord;
+use itertools::Itertools;
+
+const GLYPH_HEIGHT: usize = 6;
+
+/// Block letters as drawn by the puzzles, trimmed so that their first column has a lit pixel
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum OcrError {
+    #[error("no lit pixels to read")]
+    Empty,
+    #[error("expected letters {GLYPH_HEIGHT} pixels tall, found {0}")]
+    InvalidHeight(usize),
+    #[error("unrecognized glyph at column {column}:\n{glyph}")]
+    UnrecognizedGlyph { column: usize, glyph: String },
+}
+
+/// Lit pixels of a grid, cropped to the area that contains any of them
+struct Pixels {
+    columns: Vec<[bool; GLYPH_HEIGHT]>,
+}
+
+impl Pixels {
+    fn from_grid(grid: &Grid<bool>) -> Result<Self, OcrError> {
+        let (_origin, cropped) = grid.crop(|&pixel| pixel).ok_or(OcrError::Empty)?;
+
+        if cropped.height() != GLYPH_HEIGHT {
+            return Err(OcrError::InvalidHeight(cropped.height()));
+        }
+
+        let lit = |x: usize, y: usize| {
+            cropped
+                .read(Coord::new(x as i32, y as i32))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let columns = (0..cropped.width())
+            .map(|x| std::array::from_fn(|y| lit(x, y)))
+            .collect();
+
+        Ok(Self { columns })
+    }
+
+    fn is_blank(&self, x: usize) -> bool {
+        self.columns
+            .get(x)
+            .is_none_or(|column| column.iter().all(|&pixel| !pixel))
+    }
+
+    fn matches(&self, x: usize, glyph: &[&str; GLYPH_HEIGHT]) -> bool {
+        let width = glyph[0].len();
+
+        (0..width).all(|dx| {
+            let column = self.columns.get(x + dx).copied().unwrap_or_default();
+            glyph
+                .iter()
+                .zip(column)
+                .all(|(row, pixel)| (row.as_bytes()[dx] == b'#') == pixel)
+        })
+    }
+
+    fn render(&self, from: usize) -> String {
+        let to = (from..self.columns.len())
+            .find(|&x| self.is_blank(x))
+            .unwrap_or(self.columns.len());
+
+        (0..GLYPH_HEIGHT)
+            .map(|y| {
+                self.columns[from..to]
+                    .iter()
+                    .map(|column| if column[y] { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+/// Read the block letters drawn by the lit pixels of a grid
+pub(crate) fn read_letters(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let pixels = Pixels::from_grid(grid)?;
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < pixels.columns.len() {
+        if pixels.is_blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let (letter, glyph) = GLYPHS
+            .iter()
+            .find(|(_letter, glyph)| pixels.matches(x, glyph))
+            .ok_or_else(|| OcrError::UnrecognizedGlyph {
+                column: x,
+                glyph: pixels.render(x),
+            })?;
+
+        letters.push(*letter);
+        x += glyph[0].len();
+    }
+
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_from(lines: &[&str]) -> Grid<bool> {
+        let rows = lines
+            .iter()
+            .map(|line| line.chars().map(|c| c == '#').collect_vec())
+            .collect_vec();
+
+        Grid::new(rows).unwrap()
+    }
+
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let grid = grid_from(&[
+            ".........................",
+            ".####.#..#.#..#..##....##",
+            "....#.#..#.#.#..#..#....#",
+            "...#..#..#.##...#.......#",
+            "..#...#..#.#.#..#.......#",
+            ".#....#..#.#.#..#..#.#..#",
+            ".####..##..#..#..##...##.",
+        ]);
+
+        assert_eq!(read_letters(&grid).unwrap(), "ZUKCJ");
+    }
+
+    #[test]
+    fn unrecognized() {
+        #[rustfmt::skip]
+        let grid = grid_from(&[
+            "####.#..#",
+            "#..#.#..#",
+            "#..#.#..#",
+            "####.#..#",
+            "#..#.#..#",
+            "####..##.",
+        ]);
+
+        assert!(matches!(
+            read_letters(&grid),
+            Err(OcrError::UnrecognizedGlyph { column: 0, .. })
+        ));
+    }
+}
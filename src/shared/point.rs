@@ -0,0 +1,187 @@
+use std::array;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Integer point or vector in `N` dimensions, ordered axis by axis
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
+pub struct Point<const N: usize> {
+    axes: [i32; N],
+}
+
+impl<const N: usize> Point<N> {
+    pub(crate) const fn from_axes(axes: [i32; N]) -> Self {
+        Self { axes }
+    }
+
+    /// Point one step along the given axis
+    #[allow(dead_code)]
+    pub(crate) fn unit(axis: usize) -> Self {
+        Self::from_axes(array::from_fn(|index| i32::from(index == axis)))
+    }
+
+    pub(crate) fn axes(&self) -> [i32; N] {
+        self.axes
+    }
+
+    pub(crate) fn map(self, f: impl Fn(i32) -> i32) -> Self {
+        Self::from_axes(self.axes.map(f))
+    }
+
+    /// Sum of the absolute values of every axis
+    pub(crate) fn manhattan(&self) -> u32 {
+        self.axes.iter().map(|value| value.unsigned_abs()).sum()
+    }
+
+    /// Largest absolute value of any axis, i.e. the number of king moves from the origin
+    #[allow(dead_code)]
+    pub(crate) fn chebyshev(&self) -> u32 {
+        self.axes
+            .iter()
+            .map(|value| value.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Straight-line length from the origin
+    #[allow(dead_code)]
+    pub(crate) fn euclidean(&self) -> f64 {
+        self.axes
+            .iter()
+            .map(|&value| f64::from(value) * f64::from(value))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Each axis reduced to -1, 0 or 1
+    #[allow(dead_code)]
+    pub(crate) fn signum(self) -> Self {
+        self.map(i32::signum)
+    }
+
+    /// The `2 * N` points one step away along a single axis
+    #[allow(dead_code)]
+    pub(crate) fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            let unit = Self::unit(axis);
+            [self - unit, self + unit]
+        })
+    }
+
+    /// The `3^N - 1` points touching this one, diagonals included
+    #[allow(dead_code)]
+    pub(crate) fn neighbours(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(N as u32);
+
+        (0..count)
+            .filter(move |&index| index != count / 2)
+            .map(move |index| {
+                // Each base 3 digit of the index picks -1, 0 or 1 for one axis
+                let delta =
+                    array::from_fn(|axis| (index / 3usize.pow(axis as u32) % 3) as i32 - 1);
+                self + Self::from_axes(delta)
+            })
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::from_axes([0; N])
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i32;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.axes[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.axes[axis]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_axes(array::from_fn(|axis| self.axes[axis] + rhs.axes[axis]))
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_axes(array::from_fn(|axis| self.axes[axis] - rhs.axes[axis]))
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl<const N: usize> Mul<i32> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+impl<const N: usize> MulAssign<i32> for Point<N> {
+    fn mul_assign(&mut self, rhs: i32) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_neighbours() {
+        let mut point = Point::from_axes([1, -2, 3]);
+        assert_eq!(point * 2 - point, point);
+        assert_eq!(-point + point, Point::default());
+        assert_eq!(point.manhattan(), 6);
+        assert_eq!(point.chebyshev(), 3);
+        assert_eq!(Point::from_axes([3, -4]).euclidean(), 5.0);
+
+        point[2] -= 5;
+        point[0] = 4;
+        assert_eq!(point.axes(), [4, -2, -2]);
+        assert_eq!(point.signum(), Point::from_axes([1, -1, -1]));
+
+        let origin = Point::<4>::default();
+        assert_eq!(origin.neighbours().count(), 80);
+        assert!(
+            origin
+                .neighbours()
+                .all(|neighbour| neighbour.chebyshev() == 1)
+        );
+        assert_eq!(origin.orthogonal_neighbours().count(), 8);
+        assert!(
+            origin
+                .orthogonal_neighbours()
+                .all(|neighbour| neighbour.manhattan() == 1)
+        );
+    }
+}
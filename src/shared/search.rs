@@ -0,0 +1,396 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+pub(crate) mod compress;
+pub(crate) mod keys;
+pub(crate) mod maze;
+
+pub(crate) trait State: Sized + Clone {
+    type Position: Clone + Eq + Hash;
+
+    fn cost(&self) -> u64;
+
+    /// Estimate of the cost left to reach a final state, which must never overestimate it
+    fn heuristic(&self) -> u64;
+
+    fn total_cost(&self) -> u64 {
+        self.heuristic() + self.cost()
+    }
+
+    fn position(&self) -> Self::Position;
+
+    fn is_final(&self) -> bool;
+
+    fn neighbours(&self) -> Vec<Self>;
+
+    /// Positions from this state to the cheapest final state, both included
+    fn path(&self) -> Option<Vec<Self::Position>> {
+        a_star(self.clone()).map(Found::into_path)
+    }
+}
+
+/// Final state of a search, along with the positions it went through from the initial one
+pub(crate) struct Found<T: State> {
+    state: T,
+    path: Vec<T::Position>,
+}
+
+impl<T: State> Found<T> {
+    pub(crate) fn state(&self) -> &T {
+        &self.state
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn path(&self) -> &[T::Position] {
+        &self.path
+    }
+
+    pub(crate) fn into_path(self) -> Vec<T::Position> {
+        self.path
+    }
+}
+
+/// Position each reached position was reached from, so that states do not need to carry their
+/// own path around
+struct Parents<P> {
+    links: HashMap<P, P>,
+}
+
+impl<P: Clone + Eq + Hash> Parents<P> {
+    fn new() -> Self {
+        Self {
+            links: HashMap::new(),
+        }
+    }
+
+    fn link(&mut self, child: P, parent: P) {
+        self.links.insert(child, parent);
+    }
+
+    /// Positions from the start of the search to `end`, both included
+    fn path_to(&self, end: P) -> Vec<P> {
+        let mut path = vec![end];
+        while let Some(parent) = self.links.get(path.last().expect("Path is never empty")) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        path
+    }
+
+    fn found<T: State<Position = P>>(&self, state: T) -> Found<T> {
+        Found {
+            path: self.path_to(state.position()),
+            state,
+        }
+    }
+}
+
+/// State waiting in a search's open set, popped lowest priority first
+struct Queued<T> {
+    priority: Reverse<(u64, u64)>,
+    state: T,
+}
+
+impl<T> Queued<T> {
+    fn new(priority: u64, tie_breaker: u64, state: T) -> Self {
+        Self {
+            priority: Reverse((priority, tie_breaker)),
+            state,
+        }
+    }
+}
+
+impl<T> PartialEq for Queued<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<T> Eq for Queued<T> {}
+
+impl<T> Ord for Queued<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+impl<T> PartialOrd for Queued<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Cheapest final state, expanding states in order of `priority` and skipping those reached more
+/// cheaply already
+fn best_first<T: State>(initial: T, priority: impl Fn(&T) -> (u64, u64)) -> Option<Found<T>> {
+    let queued = |state: T| {
+        let (priority, tie_breaker) = priority(&state);
+        Queued::new(priority, tie_breaker, state)
+    };
+
+    let mut best_cost: HashMap<T::Position, u64> =
+        HashMap::from([(initial.position(), initial.cost())]);
+    let mut parents = Parents::new();
+    let mut open_set = BinaryHeap::from([queued(initial)]);
+
+    while let Some(Queued { state: node, .. }) = open_set.pop() {
+        if node.is_final() {
+            return Some(parents.found(node));
+        }
+
+        if best_cost
+            .get(&node.position())
+            .is_some_and(|&best| best < node.cost())
+        {
+            continue;
+        }
+
+        for neighbour in node.neighbours() {
+            let previous_best = best_cost.get(&neighbour.position()).copied();
+            if previous_best.is_none_or(|previous_best| previous_best > neighbour.cost()) {
+                best_cost.insert(neighbour.position(), neighbour.cost());
+                parents.link(neighbour.position(), node.position());
+                open_set.push(queued(neighbour));
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest final state, guided by the heuristic
+pub(crate) fn a_star<T: State>(initial: T) -> Option<Found<T>> {
+    // Among equally promising states, the one with the least left to go is closest to the end
+    best_first(initial, |state| (state.total_cost(), state.heuristic()))
+}
+
+/// Cheapest final state, ignoring the heuristic
+pub(crate) fn dijkstra<T: State>(initial: T) -> Option<Found<T>> {
+    best_first(initial, |state| (state.cost(), 0))
+}
+
+/// Nearest final state when every move costs the same, so that the first state reaching a
+/// position is always the cheapest there
+#[allow(dead_code)]
+pub(crate) fn bfs<T: State>(initial: T) -> Option<Found<T>> {
+    let start = initial.position();
+    let mut parents = Parents::new();
+    let mut open_set = VecDeque::from([initial]);
+
+    while let Some(node) = open_set.pop_front() {
+        if node.is_final() {
+            return Some(parents.found(node));
+        }
+
+        for neighbour in node.neighbours() {
+            let position = neighbour.position();
+            if position != start && !parents.links.contains_key(&position) {
+                parents.link(position, node.position());
+                open_set.push_back(neighbour);
+            }
+        }
+    }
+
+    None
+}
+
+/// Cost of the cheapest way to every reachable position
+#[allow(dead_code)]
+pub(crate) fn distances<T: State>(initial: T) -> HashMap<T::Position, u64> {
+    let mut best_cost: HashMap<T::Position, u64> = HashMap::new();
+    let mut open_set = BinaryHeap::from([Queued::new(initial.cost(), 0, initial)]);
+
+    while let Some(Queued { state: node, .. }) = open_set.pop() {
+        if best_cost.contains_key(&node.position()) {
+            continue;
+        }
+        best_cost.insert(node.position(), node.cost());
+
+        for neighbour in node.neighbours() {
+            if !best_cost.contains_key(&neighbour.position()) {
+                open_set.push(Queued::new(neighbour.cost(), 0, neighbour));
+            }
+        }
+    }
+
+    best_cost
+}
+
+/// Every cheapest way to a final state, as the positions it goes through
+#[allow(dead_code)]
+pub(crate) fn all_shortest_paths<T: State>(initial: T) -> Vec<Vec<T::Position>> {
+    let start = initial.position();
+    let mut best_cost: HashMap<T::Position, u64> = HashMap::from([(start.clone(), initial.cost())]);
+    // Unlike single path searches, every parent reaching a position at its best cost is kept
+    let mut parents: HashMap<T::Position, Vec<T::Position>> = HashMap::new();
+    let mut open_set = BinaryHeap::from([Queued::new(initial.cost(), 0, initial)]);
+    let mut ends = Vec::new();
+    let mut final_cost = None;
+
+    while let Some(Queued { state: node, .. }) = open_set.pop() {
+        if final_cost.is_some_and(|final_cost| node.cost() > final_cost) {
+            break;
+        }
+
+        if best_cost[&node.position()] < node.cost() {
+            continue;
+        }
+
+        if node.is_final() {
+            final_cost = Some(node.cost());
+            if !ends.contains(&node.position()) {
+                ends.push(node.position());
+            }
+            continue;
+        }
+
+        for neighbour in node.neighbours() {
+            let position = neighbour.position();
+            match best_cost.get(&position).copied() {
+                Some(best) if best < neighbour.cost() => {}
+                Some(best) if best == neighbour.cost() => {
+                    let others = parents.entry(position).or_default();
+                    if !others.contains(&node.position()) {
+                        others.push(node.position());
+                    }
+                }
+                _ => {
+                    best_cost.insert(position.clone(), neighbour.cost());
+                    parents.insert(position, vec![node.position()]);
+                    open_set.push(Queued::new(neighbour.cost(), 0, neighbour));
+                }
+            }
+        }
+    }
+
+    // Paths are built backwards, from each end towards the start
+    let mut paths = Vec::new();
+    let mut partial = ends.into_iter().map(|end| vec![end]).collect::<Vec<_>>();
+    while let Some(path) = partial.pop() {
+        let last = path.last().expect("Path is never empty");
+        if *last == start {
+            paths.push(path.into_iter().rev().collect());
+            continue;
+        }
+
+        for parent in parents.get(last).into_iter().flatten() {
+            let mut longer = path.clone();
+            longer.push(parent.clone());
+            partial.push(longer);
+        }
+    }
+
+    paths
+}
+
+/// One side of a bidirectional search
+#[allow(dead_code)]
+struct Frontier<T: State> {
+    open_set: BinaryHeap<Queued<T>>,
+    best: HashMap<T::Position, T>,
+    parents: Parents<T::Position>,
+}
+
+#[allow(dead_code)]
+impl<T: State> Frontier<T> {
+    fn new(initial: T) -> Self {
+        Self {
+            open_set: BinaryHeap::from([Queued::new(initial.cost(), 0, initial.clone())]),
+            best: HashMap::from([(initial.position(), initial)]),
+            parents: Parents::new(),
+        }
+    }
+
+    fn lowest_cost(&self) -> Option<u64> {
+        self.open_set.peek().map(|queued| queued.priority.0.0)
+    }
+
+    /// Expands the cheapest open state, returning the states that improved a position
+    fn expand(&mut self) -> Vec<T> {
+        let Some(Queued { state: node, .. }) = self.open_set.pop() else {
+            return Vec::new();
+        };
+
+        if self
+            .best
+            .get(&node.position())
+            .is_some_and(|best| best.cost() < node.cost())
+        {
+            return Vec::new();
+        }
+
+        let mut improved = Vec::new();
+        for neighbour in node.neighbours() {
+            let previous_best = self.best.get(&neighbour.position());
+            if previous_best.is_none_or(|previous_best| previous_best.cost() > neighbour.cost()) {
+                self.best.insert(neighbour.position(), neighbour.clone());
+                self.parents.link(neighbour.position(), node.position());
+                self.open_set
+                    .push(Queued::new(neighbour.cost(), 0, neighbour.clone()));
+                improved.push(neighbour);
+            }
+        }
+
+        improved
+    }
+}
+
+/// Cost of the cheapest way between two states and the positions along it, searching from both
+/// ends until the searches meet. Moves must cost the same both ways.
+#[allow(dead_code)]
+pub(crate) fn bidirectional<T: State>(start: T, goal: T) -> Option<(u64, Vec<T::Position>)> {
+    if start.position() == goal.position() {
+        return Some((start.cost() + goal.cost(), vec![start.position()]));
+    }
+
+    let mut forward = Frontier::new(start);
+    let mut backward = Frontier::new(goal);
+    let mut meeting: Option<(T, T)> = None;
+
+    while let (Some(forward_cost), Some(backward_cost)) =
+        (forward.lowest_cost(), backward.lowest_cost())
+    {
+        // Any path not found yet goes through both open sets, so costs at least this much
+        if meeting.as_ref().is_some_and(|(ahead, behind)| {
+            ahead.cost() + behind.cost() <= forward_cost + backward_cost
+        }) {
+            break;
+        }
+
+        let forward_turn = forward_cost <= backward_cost;
+        let (searching, other) = if forward_turn {
+            (&mut forward, &backward)
+        } else {
+            (&mut backward, &forward)
+        };
+
+        for reached in searching.expand() {
+            let Some(met) = other.best.get(&reached.position()) else {
+                continue;
+            };
+
+            let cost = reached.cost() + met.cost();
+            if meeting
+                .as_ref()
+                .is_none_or(|(ahead, behind)| ahead.cost() + behind.cost() > cost)
+            {
+                meeting = Some(if forward_turn {
+                    (reached, met.clone())
+                } else {
+                    (met.clone(), reached)
+                });
+            }
+        }
+    }
+
+    meeting.map(|(ahead, behind)| {
+        let mut path = forward.parents.path_to(ahead.position());
+        let mut way_back = backward.parents.path_to(behind.position());
+        way_back.pop();
+        path.extend(way_back.into_iter().rev());
+
+        (ahead.cost() + behind.cost(), path)
+    })
+}
@@ -0,0 +1,54 @@
+use crate::shared::board::{Board, BoardExt};
+use crate::shared::coord::Coord;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Board reduced to its points of interest, linked by the length of the shortest walk between
+/// them that does not go through another point of interest
+#[derive(Clone, Debug)]
+pub(crate) struct PoiGraph<P> {
+    edges: HashMap<P, Vec<(P, u64)>>,
+}
+
+impl<P: Copy + Eq + Hash> PoiGraph<P> {
+    /// Labels cells with `poi` and walks from each labelled one over `passable` cells. Points of
+    /// interest are walls of their own: they end a walk, and searches go through them as nodes.
+    pub(crate) fn compress<T: Clone, B: Board<T>>(
+        board: &B,
+        poi: impl Fn(Coord, &T) -> Option<P>,
+        passable: impl Fn(&T) -> bool,
+    ) -> Self {
+        let points = board
+            .iter()
+            .filter_map(|(coord, value)| poi(coord, value).map(|point| (coord, point)))
+            .collect::<HashMap<_, _>>();
+
+        let edges = points
+            .iter()
+            .map(|(&start, &from)| {
+                let reachable = board
+                    .bfs(start, |coord, value| {
+                        coord == start || (passable(value) && !points.contains_key(&coord))
+                    })
+                    .filter(|&(coord, _distance)| coord != start)
+                    .filter_map(|(coord, distance)| {
+                        points.get(&coord).map(|&to| (to, u64::from(distance)))
+                    })
+                    .collect();
+
+                (from, reachable)
+            })
+            .collect();
+
+        Self { edges }
+    }
+
+    pub(crate) fn points(&self) -> impl Iterator<Item = P> + '_ {
+        self.edges.keys().copied()
+    }
+
+    /// Points of interest next to this one, with the distance to each
+    pub(crate) fn edges(&self, from: P) -> &[(P, u64)] {
+        self.edges.get(&from).map_or(&[], Vec::as_slice)
+    }
+}
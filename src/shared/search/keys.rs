@@ -0,0 +1,231 @@
+use crate::shared::coord::Coord;
+use crate::shared::search::State;
+use crate::shared::search::compress::PoiGraph;
+use std::fmt::{Display, Formatter};
+
+/// Set of keys, numbered from 0 for `a` to 25 for `z`
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default, Ord, PartialOrd)]
+pub(crate) struct KeySet(u32);
+
+impl KeySet {
+    /// One key per lowercase letter
+    const CAPACITY: u8 = 26;
+
+    pub(crate) fn with(self, key: u8) -> Self {
+        debug_assert!(key < Self::CAPACITY, "key {key} is not a letter");
+        Self(self.0 | 1 << key)
+    }
+
+    pub(crate) fn contains(&self, key: u8) -> bool {
+        debug_assert!(key < Self::CAPACITY, "key {key} is not a letter");
+        self.0 & (1 << key) != 0
+    }
+
+    pub(crate) fn contains_all(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl Display for KeySet {
+    /// Keys as the lowercase letters of a day 18 map
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (0..Self::CAPACITY)
+            .filter(|&key| self.contains(key))
+            .try_for_each(|key| write!(f, "{}", char::from(b'a' + key)))
+    }
+}
+
+/// Cell of a key and door maze worth stopping at
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
+pub(crate) enum Poi {
+    Entrance(Coord),
+    Key(u8),
+    Door(u8),
+}
+
+impl Poi {
+    /// `@` for entrances, lowercase letters for keys and uppercase letters for the doors they open
+    pub(crate) fn from_char(coord: Coord, ch: char) -> Option<Self> {
+        match ch {
+            '@' => Some(Self::Entrance(coord)),
+            'a'..='z' => Some(Self::Key(ch as u8 - b'a')),
+            'A'..='Z' => Some(Self::Door(ch as u8 - b'A')),
+            _ => None,
+        }
+    }
+}
+
+/// Maze where robots pick up keys, and can only walk through a door once its key is picked up
+pub(crate) struct KeyMaze {
+    graph: PoiGraph<Poi>,
+    all_keys: KeySet,
+}
+
+impl KeyMaze {
+    pub(crate) fn new(graph: PoiGraph<Poi>) -> Self {
+        let all_keys = graph
+            .points()
+            .filter_map(|point| match point {
+                Poi::Key(key) => Some(key),
+                _ => None,
+            })
+            .fold(KeySet::default(), KeySet::with);
+
+        Self { graph, all_keys }
+    }
+
+    /// One robot on every entrance, without any key
+    pub(crate) fn initial(&self) -> KeysState<'_> {
+        let mut robots = self
+            .graph
+            .points()
+            .filter(|point| matches!(point, Poi::Entrance(_)))
+            .collect::<Vec<_>>();
+        robots.sort();
+
+        KeysState {
+            robots,
+            keys: KeySet::default(),
+            cost: 0,
+            maze: self,
+        }
+    }
+}
+
+/// Where every robot stands and which keys they picked up between them
+#[derive(Clone)]
+pub(crate) struct KeysState<'a> {
+    robots: Vec<Poi>,
+    keys: KeySet,
+    cost: u64,
+    maze: &'a KeyMaze,
+}
+
+impl KeysState<'_> {
+    #[allow(dead_code)]
+    pub(crate) fn keys(&self) -> KeySet {
+        self.keys
+    }
+}
+
+impl State for KeysState<'_> {
+    type Position = (Vec<Poi>, KeySet);
+
+    fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    fn heuristic(&self) -> u64 {
+        0
+    }
+
+    fn position(&self) -> Self::Position {
+        (self.robots.clone(), self.keys)
+    }
+
+    fn is_final(&self) -> bool {
+        self.keys.contains_all(self.maze.all_keys)
+    }
+
+    /// One robot at a time moves to a neighbouring point of interest, unless it is a locked door
+    fn neighbours(&self) -> Vec<Self> {
+        self.robots
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &robot)| {
+                self.maze
+                    .graph
+                    .edges(robot)
+                    .iter()
+                    .filter_map(move |&(to, distance)| {
+                        let keys = match to {
+                            Poi::Door(door) if !self.keys.contains(door) => return None,
+                            Poi::Key(key) => self.keys.with(key),
+                            _ => self.keys,
+                        };
+
+                        let mut robots = self.robots.clone();
+                        robots[index] = to;
+
+                        Some(Self {
+                            robots,
+                            keys,
+                            cost: self.cost + distance,
+                            maze: self.maze,
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::char_grid;
+    use crate::shared::search::dijkstra;
+
+    fn collect_all(maze: &[&str]) -> (u64, KeySet) {
+        let grid = char_grid(maze, Some).unwrap();
+        let graph = PoiGraph::compress(
+            &grid,
+            |coord, &ch| Poi::from_char(coord, ch),
+            |&ch| ch != '#',
+        );
+        let maze = KeyMaze::new(graph);
+
+        let found = dijkstra(maze.initial()).expect("Collect every key");
+        (found.state().cost(), found.state().keys())
+    }
+
+    #[test]
+    fn collect_keys() {
+        let (steps, keys) = collect_all(&["#########", "#b.A.@.a#", "#########"]);
+        assert_eq!((steps, keys.to_string()), (8, "ab".to_string()));
+
+        let (steps, keys) = collect_all(&[
+            "########################",
+            "#f.D.E.e.C.b.A.@.a.B.c.#",
+            "######################.#",
+            "#d.....................#",
+            "########################",
+        ]);
+        assert_eq!((steps, keys.len()), (86, 6));
+
+        let (steps, _keys) = collect_all(&[
+            "########################",
+            "#@..............ac.GI.b#",
+            "###d#e#f################",
+            "###A#B#C################",
+            "###g#h#i################",
+            "########################",
+        ]);
+        assert_eq!(steps, 81);
+    }
+
+    #[test]
+    #[should_panic(expected = "key 26 is not a letter")]
+    #[cfg(debug_assertions)]
+    fn key_past_z() {
+        let _ = KeySet::default().with(26);
+    }
+
+    #[test]
+    fn several_robots() {
+        let (steps, keys) = collect_all(&[
+            "#############",
+            "#DcBa.#.GhKl#",
+            "#.###@#@#I###",
+            "#e#d#####j#k#",
+            "###C#@#@###J#",
+            "#fEbA.#.FgHi#",
+            "#############",
+        ]);
+        assert_eq!((steps, keys.len()), (32, 12));
+    }
+}
@@ -1,8 +1,6 @@
-use crate::shared::a_star::State;
-use crate::shared::board::Board;
+use crate::shared::board::{Board, BoardExt};
 use crate::shared::coord::Coord;
-use std::cmp::Ordering;
-use std::hash::{Hash, Hasher};
+use crate::shared::search::State;
 
 pub(crate) trait CostCalculator<T> {
     fn calculate_cost(&self, from: (Coord, &T), to: (Coord, &T)) -> Option<u64>;
@@ -49,61 +47,32 @@ pub(crate) struct MazeState<'a, T: Clone> {
     cost: u64,
     board: &'a dyn Board<T>,
     solver: &'a MazeSolver<T>,
-    path: Vec<Coord>,
 }
 
 impl<'a, T: Clone> MazeState<'a, T> {
-    pub(crate) fn initial(coord: Coord, board: &'a dyn Board<T>, solver: &'a MazeSolver<T>) -> Self {
+    pub(crate) fn initial(
+        coord: Coord,
+        board: &'a dyn Board<T>,
+        solver: &'a MazeSolver<T>,
+    ) -> Self {
         Self {
             coord,
             cost: 0,
             board,
             solver,
-            path: Vec::new(),
         }
     }
 
     fn neighbour(&self, n_coord: Coord, extra_cost: u64) -> Self {
-        let mut n_path = self.path.clone();
-        n_path.push(self.coord);
-
         Self {
             coord: n_coord,
             cost: self.cost + extra_cost,
             board: self.board,
             solver: self.solver,
-            path: n_path,
         }
     }
 }
 
-impl<'a, T: Clone> PartialEq<Self> for MazeState<'a, T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
-    }
-}
-
-impl<'a, T: Clone> Eq for MazeState<'a, T> {}
-
-impl<'a, T: Clone> Ord for MazeState<'a, T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.cost.cmp(&other.cost).reverse()
-    }
-}
-
-impl<'a, T: Clone> PartialOrd for MazeState<'a, T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl<'a, T: Clone> Hash for MazeState<'a, T> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.coord.hash(state);
-        self.cost.hash(state);
-    }
-}
-
 impl<'a, T: Clone> State for MazeState<'a, T> {
     type Position = Coord;
 
@@ -125,14 +94,8 @@ impl<'a, T: Clone> State for MazeState<'a, T> {
 
     fn neighbours(&self) -> Vec<Self> {
         let tile = self.board.read(self.coord).expect("Read my own coord");
-        self.coord
-            .cross()
-            .filter_map(|neighbour_coord| {
-                self.board
-                    .read(neighbour_coord)
-                    .ok()
-                    .map(|tile| (neighbour_coord, tile))
-            })
+        self.board
+            .neighbours4(self.coord)
             .filter_map(|(n_coord, n_tile)| {
                 self.solver
                     .cost_calculator
@@ -142,21 +105,14 @@ impl<'a, T: Clone> State for MazeState<'a, T> {
             .map(|(n_coord, extra_cost)| self.neighbour(n_coord, extra_cost))
             .collect()
     }
-
-    fn path(&self) -> Vec<Self::Position> {
-        let mut path = self.path.clone();
-        path.push(self.coord);
-
-        path
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::shared::a_star::a_star;
-    use crate::shared::board::Grid;
+    use crate::parsers::char_map;
     use crate::shared::coord::Direction;
+    use crate::shared::search::{a_star, all_shortest_paths, bfs, bidirectional, dijkstra, distances};
     use itertools::Itertools;
 
     #[test]
@@ -173,27 +129,10 @@ mod tests {
 	        ".#S#.",
         ];
 
-        let rows = maze
-            .iter()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec();
-        let grid = Grid::new(rows).unwrap();
-
-        let start = grid
-            .elements()
-            .iter()
-            .find_map(|(coord, element)| if *element == 'S' { Some(coord) } else { None })
-            .copied()
-            .unwrap();
-
-        let end = grid
-            .elements()
-            .iter()
-            .find_map(|(coord, element)| if *element == 'E' { Some(coord) } else { None })
-            .copied()
-            .unwrap();
-
-        let grid = grid.map(|char| char != '.');
+        let map = char_map(&maze, |c| c == 'S' || c == 'E', |c| Some(c != '.')).unwrap();
+        let start = map.marker('S').unwrap();
+        let end = map.marker('E').unwrap();
+        let grid = map.grid;
 
         let maze_solver = MazeSolver::new(end, Box::new(BoolBoardCostCalculator::new()));
         let initial_state = MazeState::initial(start, &grid, &maze_solver);
@@ -221,9 +160,74 @@ mod tests {
             .collect_vec();
 
         let end_state = a_star(initial_state).expect("Find a solution to the maze");
-        assert_eq!(end_state.coord, end);
-        assert_eq!(end_state.total_cost(), 10);
-	    assert_eq!(end_state.heuristic(), 0);
+        assert_eq!(end_state.state().coord, end);
+        assert_eq!(end_state.state().total_cost(), 10);
+        assert_eq!(end_state.state().heuristic(), 0);
         assert_eq!(end_state.path(), expected_path);
+
+        let initial_state = MazeState::initial(start, &grid, &maze_solver);
+        assert_eq!(initial_state.path(), Some(end_state.into_path()));
+    }
+
+    #[test]
+    fn equivalent_searches() {
+        #[rustfmt::skip]
+        let maze = [
+            ".........",
+            ".###.###.",
+            ".#S..#E#.",
+            ".#####.#.",
+            ".......#.",
+        ];
+
+        let map = char_map(&maze, |c| c == 'S' || c == 'E', |c| Some(c != '#')).unwrap();
+        let start = map.marker('S').unwrap();
+        let end = map.marker('E').unwrap();
+        let grid = map.grid;
+
+        let maze_solver = MazeSolver::new(end, Box::new(BoolBoardCostCalculator::new()));
+        let initial = || MazeState::initial(start, &grid, &maze_solver);
+
+        // The only way around goes through the far corner, so there is a single shortest path
+        let searches = [a_star, dijkstra, bfs].map(|search| search(initial()).unwrap());
+        let path = searches[0].path();
+        assert_eq!(path.len(), 21);
+        assert!(path.iter().tuple_windows().all(|(&a, &b)| (b - a).manhattan() == 1));
+        for end_state in &searches {
+            assert_eq!(end_state.state().cost(), 20);
+            assert_eq!(end_state.path(), path);
+        }
+
+        let (cost, bidirectional_path) =
+            bidirectional(initial(), MazeState::initial(end, &grid, &maze_solver)).unwrap();
+        assert_eq!(cost, 20);
+        assert_eq!(bidirectional_path, path);
+        assert_eq!(all_shortest_paths(initial()), [path]);
+
+        let distances = distances(initial());
+        assert_eq!(distances[&end], 20);
+        assert_eq!(distances[&start], 0);
+        assert_eq!(distances[&Coord::new(4, 0)], 4);
+        assert_eq!(distances.len(), 29);
+
+        let unreachable =
+            MazeSolver::new(Coord::new(1, 1), Box::new(BoolBoardCostCalculator::new()));
+        assert!(a_star(MazeState::initial(start, &grid, &unreachable)).is_none());
+        assert!(bfs(MazeState::initial(start, &grid, &unreachable)).is_none());
+    }
+
+    #[test]
+    fn equally_short_paths() {
+        let open = ["S...", "....", "...E"];
+        let map = char_map(&open, |c| c == 'S' || c == 'E', |_c| Some(true)).unwrap();
+        let (start, end) = (map.marker('S').unwrap(), map.marker('E').unwrap());
+
+        let maze_solver = MazeSolver::new(end, Box::new(BoolBoardCostCalculator::new()));
+        let paths = all_shortest_paths(MazeState::initial(start, &map.grid, &maze_solver));
+
+        // Any order of three steps right and two steps down
+        assert_eq!(paths.len(), 10);
+        assert_eq!(paths.iter().unique().count(), 10);
+        assert!(paths.iter().all(|path| path.len() == 6 && path[0] == start && path[5] == end));
     }
 }
@@ -0,0 +1,260 @@
+use crate::shared::coord::{Coord, Direction};
+use std::fmt::{Display, Formatter};
+
+/// Range of integers, both ends included
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
+pub(crate) struct Interval {
+    start: i32,
+    end: i32,
+}
+
+impl Interval {
+    /// Interval between the two values, in whichever order they are given
+    pub(crate) fn new(a: i32, b: i32) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    pub(crate) fn start(&self) -> i32 {
+        self.start
+    }
+
+    pub(crate) fn end(&self) -> i32 {
+        self.end
+    }
+
+    /// Number of integers in the interval
+    #[allow(dead_code)]
+    pub(crate) fn len(&self) -> u64 {
+        self.end.abs_diff(self.start) as u64 + 1
+    }
+
+    pub(crate) fn contains(&self, value: i32) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+
+    /// Value of the interval closest to the given one
+    pub(crate) fn nearest(&self, value: i32) -> i32 {
+        value.clamp(self.start, self.end)
+    }
+
+    pub(crate) fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+
+        (start <= end).then_some(Self { start, end })
+    }
+}
+
+/// Union of intervals, kept sorted with touching and overlapping intervals merged
+#[allow(dead_code)]
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+#[allow(dead_code)]
+impl IntervalSet {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn insert(&mut self, interval: Interval) {
+        // Intervals before `first` end too early to touch the new one, those from `last` on start
+        // too late, and the ones in between are merged into it
+        let first = self
+            .intervals
+            .partition_point(|existing| i64::from(existing.end) + 1 < i64::from(interval.start));
+        let last = self
+            .intervals
+            .partition_point(|existing| i64::from(existing.start) <= i64::from(interval.end) + 1);
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, existing| Interval {
+                start: merged.start.min(existing.start),
+                end: merged.end.max(existing.end),
+            });
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub(crate) fn contains(&self, value: i32) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Number of integers covered by any interval
+    pub(crate) fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+}
+
+/// Horizontal or vertical line between two cells, both ends included
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub(crate) struct Segment {
+    from: Coord,
+    to: Coord,
+}
+
+impl Segment {
+    /// Segment from `from` to `to`, unless they are not on the same row or column
+    #[allow(dead_code)]
+    pub(crate) fn new(from: Coord, to: Coord) -> Option<Self> {
+        (from.x() == to.x() || from.y() == to.y()).then_some(Self { from, to })
+    }
+
+    /// Segment covered when walking `length` steps in a direction
+    pub(crate) fn walk(from: Coord, direction: Direction, length: u32) -> Self {
+        Self {
+            from,
+            to: from + Coord::from(direction) * length as i32,
+        }
+    }
+
+    pub(crate) fn from(&self) -> Coord {
+        self.from
+    }
+
+    pub(crate) fn to(&self) -> Coord {
+        self.to
+    }
+
+    /// Number of steps from one end to the other
+    pub(crate) fn len(&self) -> u32 {
+        (self.to - self.from).manhattan()
+    }
+
+    fn xs(&self) -> Interval {
+        Interval::new(self.from.x(), self.to.x())
+    }
+
+    fn ys(&self) -> Interval {
+        Interval::new(self.from.y(), self.to.y())
+    }
+
+    pub(crate) fn contains(&self, coord: Coord) -> bool {
+        self.xs().contains(coord.x()) && self.ys().contains(coord.y())
+    }
+
+    /// Steps from the start of the segment to a cell on it
+    pub(crate) fn steps_to(&self, coord: Coord) -> Option<u32> {
+        self.contains(coord)
+            .then(|| (coord - self.from).manhattan())
+    }
+
+    /// Cell of the segment nearest to `target`
+    pub(crate) fn closest_to(&self, target: Coord) -> Coord {
+        Coord::new(self.xs().nearest(target.x()), self.ys().nearest(target.y()))
+    }
+
+    /// Cells shared with the other segment, which form a single point when they cross and a
+    /// shorter segment when they overlap, running in the same direction as this one
+    pub(crate) fn intersection(&self, other: &Self) -> Option<Self> {
+        let xs = self.xs().intersection(&other.xs())?;
+        let ys = self.ys().intersection(&other.ys())?;
+
+        let (first, last) = (
+            Coord::new(xs.start(), ys.start()),
+            Coord::new(xs.end(), ys.end()),
+        );
+
+        if self.to.x() < self.from.x() || self.to.y() < self.from.y() {
+            Some(Self {
+                from: last,
+                to: first,
+            })
+        } else {
+            Some(Self {
+                from: first,
+                to: last,
+            })
+        }
+    }
+
+    /// Every cell of the segment, in order
+    #[allow(dead_code)]
+    pub(crate) fn cells(&self) -> impl Iterator<Item = Coord> {
+        let step = (self.to - self.from).signum();
+        let from = self.from;
+
+        (0..=self.len() as i32).map(move |index| from + step * index)
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn intersections() {
+        let horizontal = Segment::walk(Coord::new(-2, 1), Direction::Right, 6);
+        let vertical = Segment::walk(Coord::new(1, 5), Direction::Up, 10);
+        assert_eq!(horizontal.len(), 6);
+        assert_eq!(
+            horizontal.intersection(&vertical),
+            Segment::new(Coord::new(1, 1), Coord::new(1, 1))
+        );
+        assert_eq!(vertical.steps_to(Coord::new(1, 1)), Some(4));
+        assert_eq!(vertical.steps_to(Coord::new(2, 1)), None);
+
+        let backwards = Segment::walk(Coord::new(7, 1), Direction::Left, 6);
+        let overlap = backwards.intersection(&horizontal).unwrap();
+        assert_eq!(
+            (overlap.from(), overlap.to()),
+            (Coord::new(4, 1), Coord::new(1, 1))
+        );
+        assert_eq!(
+            overlap.cells().collect_vec(),
+            (1..=4).rev().map(|x| Coord::new(x, 1)).collect_vec()
+        );
+
+        let apart = Segment::walk(Coord::new(-2, 2), Direction::Right, 6);
+        assert_eq!(horizontal.intersection(&apart), None);
+        assert_eq!(Segment::new(Coord::new(0, 0), Coord::new(1, 1)), None);
+        assert_eq!(vertical.closest_to(Coord::default()), Coord::new(1, 0));
+    }
+
+    #[test]
+    fn interval_set() {
+        let mut set = IntervalSet::new();
+        set.insert(Interval::new(10, 12));
+        set.insert(Interval::new(1, 3));
+        set.insert(Interval::new(20, 25));
+        assert_eq!(set.len(), 12);
+
+        set.insert(Interval::new(4, 9));
+        assert_eq!(
+            set.iter().copied().collect_vec(),
+            [Interval::new(1, 12), Interval::new(20, 25)]
+        );
+        assert!(set.contains(12) && set.contains(20) && !set.contains(15) && !set.contains(0));
+
+        set.insert(Interval::new(i32::MIN, 0));
+        set.insert(Interval::new(26, i32::MAX));
+        assert_eq!(set.iter().count(), 2);
+        assert_eq!(set.len(), (1u64 << 32) - 7);
+    }
+}
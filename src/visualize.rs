@@ -0,0 +1,317 @@
+use crate::export::{Picture, Rgb};
+use crate::shared::board::{Board, Grid};
+use crate::shared::coord::Coord;
+use anyhow::Context;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+mod cast;
+mod gif;
+mod live;
+mod terminal;
+
+pub use cast::CastSink;
+pub use gif::GifSink;
+pub use live::LiveRenderer;
+pub use terminal::TerminalSink;
+
+/// Moves the cursor home and clears the terminal
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+/// Snapshot of a simulation's board, drawn both as pixels and as characters
+#[derive(Clone, Debug)]
+pub struct Frame {
+    /// Board coordinate of the top-left cell, used to line up frames of a growing board
+    origin: Coord,
+    picture: Picture,
+    glyphs: Grid<char>,
+    /// Lines of text shown under the board
+    notes: Vec<String>,
+    /// Labelled values, such as a score or a position, shown on a single status line
+    status: Vec<(String, String)>,
+    /// Tick of the simulation this frame was taken at, filled in by the recorder
+    step: usize,
+}
+
+impl Frame {
+    /// Draws every cell within the board's bounds with the character and colour picked for it
+    pub(crate) fn from_board<T: Clone, B: Board<T>>(
+        board: &B,
+        style: impl Fn(Coord, &T) -> (char, Rgb),
+    ) -> Result<Self, anyhow::Error> {
+        let (top_left, _bottom_right) = board.bounds().context("Cannot draw an empty board")?;
+        let picture = Picture::from_board(board, |coord, value| style(coord, value).1)?;
+
+        let glyphs = Grid::from_fn(picture.width(), picture.height(), |offset| {
+            let coord = top_left + offset;
+            board
+                .read(coord)
+                .map(|value| style(coord, value).0)
+                .unwrap_or(' ')
+        });
+
+        Ok(Self {
+            origin: top_left,
+            picture,
+            glyphs,
+            notes: Vec::new(),
+            status: Vec::new(),
+            step: 0,
+        })
+    }
+
+    pub(crate) fn with_note(mut self, note: impl Display) -> Self {
+        self.notes.extend(note.to_string().lines().map(str::to_string));
+        self
+    }
+
+    pub(crate) fn with_status(mut self, label: &str, value: impl Display) -> Self {
+        self.status.push((label.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    pub fn picture(&self) -> &Picture {
+        &self.picture
+    }
+
+    pub(crate) fn glyphs(&self) -> &Grid<char> {
+        &self.glyphs
+    }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Step count followed by the labelled values, e.g. `Step 12 | Score: 300`
+    pub fn status_line(&self) -> String {
+        std::iter::once(format!("Step {}", self.step))
+            .chain(
+                self.status
+                    .iter()
+                    .map(|(label, value)| format!("{label}: {value}")),
+            )
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Board, notes and status line as plain text
+    pub fn text(&self) -> String {
+        let mut text = self.glyphs.to_string();
+        for note in &self.notes {
+            text.push_str(note);
+            text.push('\n');
+        }
+        text.push_str(&self.status_line());
+        text.push('\n');
+
+        text
+    }
+}
+
+/// Destination for recorded frames
+pub trait FrameSink {
+    fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error>;
+
+    /// Called once after the last frame, for sinks that only write out at the end
+    fn finish(&mut self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+}
+
+/// Hands every `every`-th tick of a simulation to a sink, along with the final one
+pub struct Recorder {
+    sink: Box<dyn FrameSink>,
+    every: usize,
+    ticks: usize,
+    last_recorded: Option<usize>,
+}
+
+impl Recorder {
+    pub fn new(sink: Box<dyn FrameSink>, every: usize) -> Self {
+        Self {
+            sink,
+            every: every.max(1),
+            ticks: 0,
+            last_recorded: None,
+        }
+    }
+
+    /// Counts a tick, only drawing the frame when it is kept
+    pub(crate) fn tick(
+        &mut self,
+        frame: impl FnOnce() -> Result<Frame, anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        let tick = self.ticks;
+        self.ticks += 1;
+
+        if tick.is_multiple_of(self.every) {
+            self.record(tick, frame()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the state the simulation ended in, which must also have gone through `tick`, when
+    /// that tick was skipped
+    pub(crate) fn last(
+        &mut self,
+        frame: impl FnOnce() -> Result<Frame, anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        let tick = self.ticks.saturating_sub(1);
+
+        if self.last_recorded != Some(tick) {
+            self.record(tick, frame()?)?;
+        }
+
+        Ok(())
+    }
+
+    fn record(&mut self, tick: usize, mut frame: Frame) -> Result<(), anyhow::Error> {
+        frame.step = tick;
+        self.sink.record(&frame)?;
+        self.last_recorded = Some(tick);
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(), anyhow::Error> {
+        self.sink.finish()
+    }
+}
+
+/// Live, coloured playback of the frames on standard output
+pub fn watch(fps: u16) -> Box<dyn FrameSink> {
+    Box::new(LiveRenderer::new(std::io::stdout(), frame_interval(fps)))
+}
+
+fn frame_interval(fps: u16) -> Duration {
+    Duration::from_secs_f64(1.0 / f64::from(fps.max(1)))
+}
+
+/// Sink for the given output: a `.gif` animation, an asciinema `.cast`, or playback on the
+/// terminal when there is no path
+pub fn sink_for(
+    path: Option<&Path>,
+    fps: u16,
+    scale: usize,
+) -> Result<Box<dyn FrameSink>, anyhow::Error> {
+    let interval = frame_interval(fps);
+
+    let Some(path) = path else {
+        return Ok(Box::new(TerminalSink::new(std::io::stdout(), interval)));
+    };
+
+    let file = LazyFile::new(path);
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gif") => Ok(Box::new(GifSink::new(file, interval, scale))),
+        Some("cast") => Ok(Box::new(CastSink::new(file, interval))),
+        _ => Err(anyhow::anyhow!(
+            "Unknown animation format for {}, expected .gif or .cast",
+            path.display()
+        )),
+    }
+}
+
+/// File only created on the first write, so that nothing is left behind when the day turns out to
+/// have nothing to record
+struct LazyFile {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl LazyFile {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            writer: None,
+        }
+    }
+
+    fn writer(&mut self) -> std::io::Result<&mut BufWriter<File>> {
+        if self.writer.is_none() {
+            self.writer = Some(BufWriter::new(File::create(&self.path)?));
+        }
+
+        Ok(self.writer.as_mut().expect("Writer was just created"))
+    }
+}
+
+impl Write for LazyFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer()?.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::board::HashBoard;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Collect(Rc<RefCell<Vec<String>>>);
+
+    impl FrameSink for Collect {
+        fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error> {
+            self.0.borrow_mut().push(frame.text());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sampled_recording() {
+        let recorded = |ticks: usize| {
+            let texts = Rc::new(RefCell::new(Vec::new()));
+            let mut recorder = Recorder::new(Box::new(Collect(texts.clone())), 3);
+            let mut board = HashBoard::new(false);
+
+            let frame = |board: &HashBoard<bool>, tick: usize| {
+                Frame::from_board(board, |_coord, &lit| {
+                    if lit {
+                        ('#', Rgb::WHITE)
+                    } else {
+                        ('.', Rgb::BLACK)
+                    }
+                })
+                .map(|frame| frame.with_note(tick))
+            };
+
+            for tick in 0..ticks {
+                board
+                    .write(Coord::new(tick as i32, 0), tick % 2 == 0)
+                    .unwrap();
+                recorder.tick(|| frame(&board, tick)).unwrap();
+            }
+            recorder.last(|| frame(&board, ticks - 1)).unwrap();
+            recorder.finish().unwrap();
+
+            texts.take()
+        };
+
+        assert_eq!(
+            recorded(7),
+            ["#\n0\nStep 0\n", "#.#.\n3\nStep 3\n", "#.#.#.#\n6\nStep 6\n"]
+        );
+        assert_eq!(recorded(5).last().unwrap(), "#.#.#\n4\nStep 4\n");
+        assert_eq!(recorded(5).len(), 3);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::visualize::{CLEAR_SCREEN, Frame, FrameSink};
+use anyhow::Context;
+use std::io::Write;
+use std::time::Duration;
+
+/// Recording in the asciinema v2 format, written at the end since its header needs the size of
+/// the largest frame
+pub struct CastSink<W: Write> {
+    writer: Option<W>,
+    interval: Duration,
+    screens: Vec<String>,
+}
+
+impl<W: Write> CastSink<W> {
+    pub fn new(writer: W, interval: Duration) -> Self {
+        Self {
+            writer: Some(writer),
+            interval,
+            screens: Vec::new(),
+        }
+    }
+}
+
+impl<W: Write> FrameSink for CastSink<W> {
+    fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error> {
+        self.screens.push(frame.text());
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), anyhow::Error> {
+        let mut writer = self.writer.take().context("Recording already written")?;
+
+        let lines = || self.screens.iter().flat_map(|screen| screen.lines());
+        let width = lines().map(|line| line.chars().count()).max().unwrap_or_default();
+        let height = self
+            .screens
+            .iter()
+            .map(|screen| screen.lines().count())
+            .max()
+            .unwrap_or_default();
+
+        let header = serde_json::json!({ "version": 2, "width": width, "height": height });
+        writeln!(writer, "{header}")?;
+
+        for (index, screen) in self.screens.iter().enumerate() {
+            let time = self.interval.as_secs_f64() * index as f64;
+            let output = format!("{CLEAR_SCREEN}{}", screen.replace('\n', "\r\n"));
+            writeln!(writer, "{}", serde_json::json!([time, "o", output]))?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::Rgb;
+    use crate::shared::board::{Board, HashBoard};
+    use crate::shared::coord::Coord;
+
+    #[test]
+    fn recording() {
+        let mut bytes = Vec::new();
+        let mut sink = CastSink::new(&mut bytes, Duration::from_millis(500));
+        let mut board = HashBoard::new(false);
+
+        for x in 0..2 {
+            board.write(Coord::new(x, 0), true).unwrap();
+            let frame = Frame::from_board(&board, |_coord, &lit| {
+                if lit {
+                    ('#', Rgb::WHITE)
+                } else {
+                    ('.', Rgb::BLACK)
+                }
+            })
+            .unwrap()
+            .with_note(x);
+            sink.record(&frame).unwrap();
+        }
+        sink.finish().unwrap();
+
+        let cast = String::from_utf8(bytes).unwrap();
+        let lines: Vec<serde_json::Value> = cast
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                serde_json::json!({ "version": 2, "width": 6, "height": 3 }),
+                serde_json::json!([0.0, "o", "\x1b[H\x1b[2J#\r\n0\r\nStep 0\r\n"]),
+                serde_json::json!([0.5, "o", "\x1b[H\x1b[2J##\r\n1\r\nStep 0\r\n"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::export::Rgb;
+use crate::shared::coord::Coord;
+use crate::visualize::{Frame, FrameSink};
+use anyhow::Context;
+use itertools::Itertools;
+use std::io::Write;
+use std::time::Duration;
+
+/// Animated GIF, kept in memory until the end so every frame shares a canvas covering all of them
+pub struct GifSink<W: Write> {
+    writer: Option<W>,
+    delay: u16,
+    scale: usize,
+    frames: Vec<Frame>,
+}
+
+impl<W: Write> GifSink<W> {
+    pub fn new(writer: W, interval: Duration, scale: usize) -> Self {
+        // GIF delays are counted in hundredths of a second
+        let delay = (interval.as_millis() / 10).clamp(1, u16::MAX.into()) as u16;
+
+        Self {
+            writer: Some(writer),
+            delay,
+            scale: scale.max(1),
+            frames: Vec::new(),
+        }
+    }
+
+    fn canvas_bounds(&self) -> Option<(Coord, Coord)> {
+        let corners = self.frames.iter().flat_map(|frame| {
+            let size = Coord::new(
+                frame.picture().width() as i32 - 1,
+                frame.picture().height() as i32 - 1,
+            );
+            [frame.origin(), frame.origin() + size]
+        });
+
+        let (min_x, max_x) = corners.clone().map(|coord| coord.x()).minmax().into_option()?;
+        let (min_y, max_y) = corners.map(|coord| coord.y()).minmax().into_option()?;
+
+        Some((Coord::new(min_x, min_y), Coord::new(max_x, max_y)))
+    }
+}
+
+impl<W: Write> FrameSink for GifSink<W> {
+    fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error> {
+        self.frames.push(frame.clone());
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), anyhow::Error> {
+        let writer = self.writer.take().context("Animation already written")?;
+        let (top_left, bottom_right) = self.canvas_bounds().context("No frames were recorded")?;
+
+        let width = (bottom_right.x() - top_left.x() + 1) as usize;
+        let height = (bottom_right.y() - top_left.y() + 1) as usize;
+        let (canvas_width, canvas_height) = (width * self.scale, height * self.scale);
+        let too_large = || anyhow::anyhow!("Animation of {canvas_width}x{canvas_height} is too large");
+
+        let mut encoder = ::gif::Encoder::new(
+            writer,
+            u16::try_from(canvas_width).map_err(|_| too_large())?,
+            u16::try_from(canvas_height).map_err(|_| too_large())?,
+            &[],
+        )?;
+        encoder.set_repeat(::gif::Repeat::Infinite)?;
+
+        for frame in &self.frames {
+            let offset = frame.origin() - top_left;
+            let pixels = frame.picture().pixels();
+            let mut canvas = vec![Rgb::BLACK; width * height];
+
+            for (y, row) in pixels.rows().enumerate() {
+                let start = (offset.y() as usize + y) * width + offset.x() as usize;
+                canvas[start..start + row.len()].copy_from_slice(row);
+            }
+
+            let bytes = (0..canvas_height)
+                .flat_map(|y| (0..canvas_width).map(move |x| (x, y)))
+                .flat_map(|(x, y)| {
+                    let Rgb(r, g, b) = canvas[(y / self.scale) * width + x / self.scale];
+                    [r, g, b]
+                })
+                .collect_vec();
+
+            let mut gif_frame = ::gif::Frame::from_rgb_speed(
+                canvas_width as u16,
+                canvas_height as u16,
+                &bytes,
+                10,
+            );
+            gif_frame.delay = self.delay;
+            encoder.write_frame(&gif_frame)?;
+        }
+
+        encoder.into_inner()?.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::board::{Board, HashBoard};
+
+    #[test]
+    fn animation() {
+        let mut bytes = Vec::new();
+        let mut sink = GifSink::new(&mut bytes, Duration::from_millis(250), 2);
+        let mut board = HashBoard::new(false);
+
+        // The board grows diagonally, so the canvas has to cover the last frame: 3x3 cells
+        for x in 0..3 {
+            board.write(Coord::new(x, x - 1), true).unwrap();
+            let frame = Frame::from_board(&board, |_coord, &lit| {
+                if lit {
+                    ('#', Rgb::WHITE)
+                } else {
+                    ('.', Rgb::BLACK)
+                }
+            })
+            .unwrap();
+            sink.record(&frame).unwrap();
+        }
+        sink.finish().unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF89a");
+        // Logical screen width and height, little-endian, scaled twice
+        assert_eq!(&bytes[6..10], [6, 0, 6, 0]);
+
+        let mut decoder = ::gif::DecodeOptions::new()
+            .read_info(bytes.as_slice())
+            .unwrap();
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, [25, 25, 25]);
+    }
+}
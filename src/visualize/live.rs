@@ -0,0 +1,144 @@
+use crate::export::Rgb;
+use crate::visualize::{CLEAR_SCREEN, Frame, FrameSink};
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_LINE_END: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+const REVERSE: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// Redraws each frame in place with coloured cells and a status line, waiting between frames so
+/// that they come at a steady rate
+pub struct LiveRenderer<W: Write> {
+    out: W,
+    interval: Duration,
+    next_draw: Option<Instant>,
+}
+
+impl<W: Write> LiveRenderer<W> {
+    pub fn new(out: W, interval: Duration) -> Self {
+        Self {
+            out,
+            interval,
+            next_draw: None,
+        }
+    }
+
+    /// Whole screen as a single string, so that it reaches the terminal in one write
+    fn screen(&self, frame: &Frame) -> String {
+        let mut screen = String::new();
+
+        if self.next_draw.is_none() {
+            screen.push_str(CLEAR_SCREEN);
+            screen.push_str(HIDE_CURSOR);
+        }
+        screen.push_str(CURSOR_HOME);
+
+        let colors = frame.picture().pixels().rows();
+        for (glyphs, colors) in frame.glyphs().rows().zip(colors) {
+            let mut current = None;
+
+            for (&glyph, &color) in glyphs.iter().zip(colors) {
+                if current != Some(color) {
+                    let Rgb(r, g, b) = color;
+                    let _ = write!(screen, "\x1b[38;2;{r};{g};{b}m");
+                    current = Some(color);
+                }
+                screen.push(glyph);
+            }
+
+            let _ = writeln!(screen, "{RESET}{CLEAR_LINE_END}");
+        }
+
+        for note in frame.notes() {
+            let _ = writeln!(screen, "{note}{CLEAR_LINE_END}");
+        }
+        let _ = writeln!(
+            screen,
+            "{REVERSE}{}{RESET}{CLEAR_LINE_END}",
+            frame.status_line()
+        );
+        screen.push_str(CLEAR_BELOW);
+
+        screen
+    }
+}
+
+impl<W: Write> FrameSink for LiveRenderer<W> {
+    fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error> {
+        if let Some(next_draw) = self.next_draw {
+            std::thread::sleep(next_draw.saturating_duration_since(Instant::now()));
+        }
+
+        let screen = self.screen(frame);
+        self.out.write_all(screen.as_bytes())?;
+        self.out.flush()?;
+        self.next_draw = Some(Instant::now() + self.interval);
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), anyhow::Error> {
+        if self.next_draw.take().is_some() {
+            write!(self.out, "{SHOW_CURSOR}")?;
+            self.out.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for LiveRenderer<W> {
+    /// Gives the cursor back even when the simulation stopped with an error
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::board::{Board, HashBoard};
+    use crate::shared::coord::Coord;
+
+    #[test]
+    fn redraws_in_place() {
+        let mut out = Vec::new();
+        let mut renderer = LiveRenderer::new(&mut out, Duration::ZERO);
+        let mut board = HashBoard::new(false);
+
+        for x in 0..2 {
+            board.write(Coord::new(x, 0), true).unwrap();
+            let frame = Frame::from_board(&board, |_coord, &lit| {
+                if lit {
+                    ('#', Rgb::WHITE)
+                } else {
+                    ('.', Rgb::BLACK)
+                }
+            })
+            .unwrap()
+            .with_status("Lit", x + 1);
+            renderer.record(&frame).unwrap();
+        }
+        drop(renderer);
+
+        let white = "\x1b[38;2;255;255;255m";
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            [
+                format!("{CLEAR_SCREEN}{HIDE_CURSOR}{CURSOR_HOME}"),
+                format!("{white}#{RESET}{CLEAR_LINE_END}\n"),
+                format!("{REVERSE}Step 0 | Lit: 1{RESET}{CLEAR_LINE_END}\n{CLEAR_BELOW}"),
+                format!("{CURSOR_HOME}{white}##{RESET}{CLEAR_LINE_END}\n"),
+                format!("{REVERSE}Step 0 | Lit: 2{RESET}{CLEAR_LINE_END}\n{CLEAR_BELOW}"),
+                SHOW_CURSOR.to_string(),
+            ]
+            .concat()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::visualize::{CLEAR_SCREEN, Frame, FrameSink};
+use std::io::Write;
+use std::time::Duration;
+
+/// Plays frames back as they come, redrawing the whole screen each time
+pub struct TerminalSink<W: Write> {
+    out: W,
+    interval: Duration,
+}
+
+impl<W: Write> TerminalSink<W> {
+    pub fn new(out: W, interval: Duration) -> Self {
+        Self { out, interval }
+    }
+}
+
+impl<W: Write> FrameSink for TerminalSink<W> {
+    fn record(&mut self, frame: &Frame) -> Result<(), anyhow::Error> {
+        write!(self.out, "{CLEAR_SCREEN}{}", frame.text())?;
+        self.out.flush()?;
+        std::thread::sleep(self.interval);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::Rgb;
+    use crate::shared::board::Grid;
+
+    #[test]
+    fn plays_back_text() {
+        let mut out = Vec::new();
+        let mut sink = TerminalSink::new(&mut out, Duration::ZERO);
+        let grid = Grid::new(vec![vec![true, false], vec![false, true]]).unwrap();
+
+        let frame = Frame::from_board(&grid, |_coord, &lit| {
+            if lit {
+                ('#', Rgb::WHITE)
+            } else {
+                ('.', Rgb::BLACK)
+            }
+        })
+        .unwrap()
+        .with_note("diagonal");
+        sink.record(&frame).unwrap();
+        sink.record(&frame).unwrap();
+
+        let screen = format!("{CLEAR_SCREEN}#.\n.#\ndiagonal\nStep 0\n");
+        assert_eq!(String::from_utf8(out).unwrap(), screen.repeat(2));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::common::outputs::{ScenarioOutput, read_expected_outputs};
+use aoc2019::day::params::Params;
+use aoc2019::day::solutions::get_day;
+use aoc2019::day::{DayPart, DaySolver};
+use aoc2019::input::{InputSource, data_root};
+
+struct Case {
+    day_solver: Box<dyn DaySolver>,
+    scenario: ScenarioOutput,
+    params: Params,
+    input: String,
+}
+
+impl Case {
+    fn load(day: u32, scenario_name: &str) -> anyhow::Result<Self> {
+        let data_root = data_root(None);
+        let day_solver = get_day(day)?;
+
+        let scenario = read_expected_outputs(&data_root, day)?
+            .ok_or_else(|| anyhow::anyhow!("No expected outputs for day {day}"))?
+            .take_scenario(scenario_name)
+            .ok_or_else(|| anyhow::anyhow!("No scenario {scenario_name} for day {day}"))?;
+
+        let source = InputSource::from_scenario(scenario_name)?;
+        let input = source.read(&data_root, day)?;
+
+        let mut params = if source.is_example() {
+            day_solver.example_parameters()
+        } else {
+            day_solver.parameters()
+        };
+        scenario.apply_params(&mut params)?;
+
+        Ok(Self {
+            day_solver,
+            scenario,
+            params,
+            input,
+        })
+    }
+
+    fn input(&self) -> Vec<&str> {
+        self.input.lines().collect()
+    }
+
+    fn expected(&self, part: DayPart) -> anyhow::Result<String> {
+        self.scenario
+            .get_part(part)
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow::anyhow!("No expected output for part {part}"))
+    }
+}
+
+pub(crate) fn run_case(day: u32, scenario_name: &str, part: DayPart) -> anyhow::Result<()> {
+    let case = Case::load(day, scenario_name)?;
+    let expected = case.expected(part)?;
+
+    let actual = case
+        .day_solver
+        .solve_part(part, &case.params, &case.input())?
+        .to_string();
+
+    assert_eq!(actual, expected, "Day {day} part {part} scenario={scenario_name}");
+
+    let streamed = case
+        .day_solver
+        .solve_stream(part, &case.params, &mut case.input.as_bytes())?
+        .to_string();
+
+    assert_eq!(
+        streamed, expected,
+        "Day {day} part {part} streamed, scenario={scenario_name}"
+    );
+
+    Ok(())
+}
+
+pub(crate) fn run_both_case(day: u32, scenario_name: &str) -> anyhow::Result<()> {
+    let case = Case::load(day, scenario_name)?;
+    let (part1, part2) = case.day_solver.solve_both(&case.params, &case.input())?;
+
+    for (part, actual) in DayPart::values().zip([part1, part2]) {
+        let expected = case.expected(part)?;
+        assert_eq!(
+            actual.to_string(),
+            expected,
+            "Day {day} both parts, part {part} scenario={scenario_name}"
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use aoc2019::day::DayPart;
+use aoc2019::day::params::Params;
+use aoc2019::input::day_dir;
 use serde::Deserialize;
 use serde_json;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fs::File;
 use std::path::{Path, PathBuf};
@@ -28,9 +31,24 @@ impl Display for Value {
 pub(crate) struct ScenarioOutput {
     part1: Option<Value>,
     part2: Option<Value>,
+    #[serde(default)]
+    params: BTreeMap<String, serde_json::Value>,
 }
 
 impl ScenarioOutput {
+    /// Apply this scenario's parameter overrides on top of a day's defaults
+    pub(crate) fn apply_params(&self, params: &mut Params) -> anyhow::Result<()> {
+        for (name, value) in &self.params {
+            let raw = match value {
+                serde_json::Value::String(raw) => raw.clone(),
+                other => other.to_string(),
+            };
+            params.set(name, &raw)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn get_part(&self, part: DayPart) -> Option<&Value> {
         match part {
             DayPart::Part1 => self.part1.as_ref(),
@@ -39,33 +57,28 @@ impl ScenarioOutput {
     }
 }
 
+/// Expected outputs keyed by scenario name, which also names the input file (`real`, `example2`...)
 #[derive(Debug, Deserialize)]
+#[serde(transparent)]
 pub(crate) struct ExpectedOutput {
-    real: Option<ScenarioOutput>,
-    example: Option<ScenarioOutput>,
+    scenarios: BTreeMap<String, ScenarioOutput>,
 }
 
 impl ExpectedOutput {
-    pub(crate) fn get_scenario(&self, example: bool) -> Option<&ScenarioOutput> {
-        if example {
-            self.example.as_ref()
-        } else {
-            self.real.as_ref()
-        }
+    pub(crate) fn take_scenario(mut self, name: &str) -> Option<ScenarioOutput> {
+        self.scenarios.remove(name)
     }
 }
 
-fn get_day_output_path(day: u32) -> Box<Path> {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("data/")
-        .join("days/")
-        .join(format!("day{day}"))
-        .join("outputs.json")
-        .into_boxed_path()
+fn get_day_output_path(data_root: &Path, day: u32) -> PathBuf {
+    day_dir(data_root, day).join("outputs.json")
 }
 
-pub(crate) fn read_expected_outputs(day: u32) -> anyhow::Result<Option<ExpectedOutput>> {
-    let file = get_day_output_path(day);
+pub(crate) fn read_expected_outputs(
+    data_root: &Path,
+    day: u32,
+) -> anyhow::Result<Option<ExpectedOutput>> {
+    let file = get_day_output_path(data_root, day);
     if !file.exists() {
         return Ok(None);
     }